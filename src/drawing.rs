@@ -1,78 +1,142 @@
-use std::collections::HashSet;
+//! Widgets drawn over the rain. Each one knows its `Size` and draws itself
+//! at a given spot with termion escape sequences, returning the cells it
+//! covered so the rain can be kept out of them.
+
+use chrono::{Datelike, NaiveDate};
 use std::io::Write;
-use termion::cursor;
+use termion::{color, cursor, style};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-type Digit = fonts::Doom;
-use fonts::Font;
+mod cache;
+mod figlet;
+mod fonts;
+mod layout;
+mod mask;
+mod placer;
+mod text;
+pub use cache::Cached;
+pub use figlet::FigletFont;
+pub use fonts::{Colossal, Doom, Font};
+pub use layout::{Aligned, FixedSize, HStack, Padding, VStack};
+pub use mask::{Mask, Rect};
+pub use placer::Placer;
+use text::Span;
 
+/// Text in big letters from a `Font`.
 pub struct Numbers {
-    digits: String,
+    rows: Vec<String>,
     size: Size,
+    style: Style,
 }
 
 impl Numbers {
-    pub fn from(digits: &str) -> Self {
-        let mut out = Self::new();
-        out.setup(digits);
-        out
+    /// `text` in `font`, as many rows tall as its letters.
+    pub fn from(text: &str, font: &dyn Font) -> Self {
+        let mut rows = font.render(text);
+        // descender rows are only kept when something uses them
+        while rows.len() > 1 && rows[rows.len() - 1].trim().is_empty() {
+            rows.pop();
+        }
+        // for better padding
+        let rows: Vec<String> = rows.into_iter().map(|row| format!(" {} ", row)).collect();
+        let width = rows.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+        Self {
+            size: Size {
+                height: rows.len().saturating_sub(1) as u16,
+                width: width as u16,
+            },
+            rows,
+            style: Style::default(),
+        }
     }
+    /// Pads the digits out to at least `width` columns.
     pub fn with_min_width(mut self, width: u16) -> Self {
         if self.size.width < width {
             self.size.width = width;
         }
         self
     }
-    fn new() -> Self {
-        Self {
-            digits: String::new(),
-            size: Size {
-                height: 0,
-                width: 0,
-            },
+    /// Colors and attributes for the digits.
+    pub fn with_style(self, style: Style) -> Self { Self { style, ..self } }
+}
+
+/// Colors and attributes for a widget's cells. Whatever isn't set is left as
+/// the widget around it has it, so a frame's background shows through.
+#[derive(Copy, Clone, Default)]
+pub struct Style {
+    /// Text color, `None` keeps the terminal's.
+    pub fg: Option<&'static dyn color::Color>,
+    /// Background color, `None` keeps the terminal's.
+    pub bg: Option<&'static dyn color::Color>,
+    /// Bold text.
+    pub bold: bool,
+    /// Italic text, on terminals that have it.
+    pub italic: bool,
+    /// Underlined text.
+    pub underline: bool,
+}
+
+impl Style {
+    // escape sequences that turn this style on
+    fn on(&self) -> String {
+        let mut on = String::new();
+        if let Some(c) = self.fg {
+            on.push_str(&color::Fg(c).to_string());
+        }
+        if let Some(c) = self.bg {
+            on.push_str(&color::Bg(c).to_string());
+        }
+        if self.bold {
+            on.push_str(style::Bold.as_ref());
         }
+        if self.italic {
+            on.push_str(style::Italic.as_ref());
+        }
+        if self.underline {
+            on.push_str(style::Underline.as_ref());
+        }
+        on
     }
-    fn setup(&mut self, new: &str) {
-        let mut w = 0;
-        let mut h = 0;
-        for digit in new.chars() {
-            let size = match digit {
-                '1' => Digit::one(),
-                '2' => Digit::two(),
-                '3' => Digit::three(),
-                '4' => Digit::four(),
-                '5' => Digit::five(),
-                '6' => Digit::six(),
-                '7' => Digit::seven(),
-                '8' => Digit::eight(),
-                '9' => Digit::nine(),
-                '0' => Digit::zero(),
-                ':' => Digit::colon(),
-                'P' => Digit::p(),
-                'A' => Digit::a(),
-                'M' => Digit::m(),
-                ' ' => Digit::space(),
-                _ => panic!("Bad Numbers input"),
-            }
-            .0;
-            w += size.width;
-            h = size.height;
+
+    // and off again, only what `on` changed
+    fn off(&self) -> String {
+        let mut off = String::new();
+        if self.fg.is_some() {
+            off.push_str(&color::Fg(color::Reset).to_string());
+        }
+        if self.bg.is_some() {
+            off.push_str(&color::Bg(color::Reset).to_string());
+        }
+        if self.bold {
+            // `style::NoBold` is double underline on some terminals
+            off.push_str(style::NoFaint.as_ref());
+        }
+        if self.italic {
+            off.push_str(style::NoItalic.as_ref());
         }
-        self.digits = new.to_owned();
-        self.size.width = w + new.len() as u16;
-        self.size.height = h - 1;
+        if self.underline {
+            off.push_str(style::NoUnderline.as_ref());
+        }
+        off
     }
 }
 
+/// How much room a widget takes up.
 #[derive(Copy, Clone)]
 pub struct Size {
+    // the offset from the top row to the bottom one, one less than the rows
     height: u16,
     width: u16,
 }
 
 impl Size {
-    pub fn height(&self) -> u16 { self.height }
+    /// A size `rows` rows tall and `width` columns wide.
+    pub fn new(rows: u16, width: u16) -> Self { Self::from(rows.saturating_sub(1), width) }
+    /// How many rows it takes up.
+    pub fn rows(&self) -> u16 { self.height + 1 }
+    /// How many columns it takes up.
     pub fn width(&self) -> u16 { self.width }
-    fn from(h: u16, w: u16) -> Self {
+    pub(crate) fn from(h: u16, w: u16) -> Self {
         Self {
             height: h,
             width: w,
@@ -80,67 +144,153 @@ impl Size {
     }
 }
 
+/// Where on the screen, or in a box, a widget goes.
+#[derive(Copy, Clone)]
+pub enum Anchor {
+    /// The top left corner.
+    TopLeft,
+    /// The middle of the top edge.
+    Top,
+    /// The top right corner.
+    TopRight,
+    /// The middle of the left edge.
+    Left,
+    /// The middle of everything.
+    Center,
+    /// The middle of the right edge.
+    Right,
+    /// The bottom left corner.
+    BottomLeft,
+    /// The middle of the bottom edge.
+    Bottom,
+    /// The bottom right corner.
+    BottomRight,
+}
+
+impl Anchor {
+    /// Where to draw something of `size` on a `screen` sized terminal, kept
+    /// `margin` cells in from the edges it's anchored to. Centered axes use
+    /// the margin to shift right or down. `None` when it can't fit at all.
+    pub fn place(self, size: Size, margin: (u16, u16), screen: (u16, u16)) -> Option<(u16, u16)> {
+        use Anchor::*;
+        // same cells `Rect::covering` covers
+        let (width, height) = (size.width, size.height + 1);
+        if width > screen.0 || height > screen.1 {
+            return None;
+        }
+        let (h, v) = match self {
+            TopLeft => (Edge::Start, Edge::Start),
+            Top => (Edge::Middle, Edge::Start),
+            TopRight => (Edge::End, Edge::Start),
+            Left => (Edge::Start, Edge::Middle),
+            Center => (Edge::Middle, Edge::Middle),
+            Right => (Edge::End, Edge::Middle),
+            BottomLeft => (Edge::Start, Edge::End),
+            Bottom => (Edge::Middle, Edge::End),
+            BottomRight => (Edge::End, Edge::End),
+        };
+        Some((
+            h.align(width, margin.0, screen.0),
+            v.align(height, margin.1, screen.1),
+        ))
+    }
+}
+
+enum Edge {
+    Start,
+    Middle,
+    End,
+}
+
+impl Edge {
+    // 1-based start of `len` cells within `space`, clamped to stay inside it.
+    fn align(&self, len: u16, margin: u16, space: u16) -> u16 {
+        let free = space - len;
+        let offset = match self {
+            Edge::Start => margin,
+            Edge::Middle => free / 2 + margin,
+            Edge::End => free.saturating_sub(margin),
+        };
+        1 + offset.min(free)
+    }
+}
+
+/// Something that can be drawn over the rain.
 pub trait Draw {
+    /// How much room it needs.
     fn size(&self) -> Size;
-    fn draw(&self, writer: &mut dyn Write, x: u16, y: u16) -> std::io::Result<HashSet<(u16, u16)>>;
+    /// Draws with the top left corner at (`x`, `y`), returning what it covered.
+    fn draw(&self, writer: &mut dyn Write, x: u16, y: u16) -> std::io::Result<Vec<Rect>>;
+    /// The cells that have something in them when drawn at (`x`, `y`), the
+    /// rest may let the rain through. All of them unless a widget knows better.
+    fn glyphs(&self, x: u16, y: u16) -> Vec<Rect> { vec![Rect::covering(x, y, self.size())] }
 }
 
 impl Draw for Numbers {
     fn size(&self) -> Size { self.size }
-    fn draw(&self, writer: &mut dyn Write, x: u16, y: u16) -> std::io::Result<HashSet<(u16, u16)>> {
-        let mut taken = HashSet::new();
-        for row in 0..(Digit::one().1).inner().len() {
-            write!(writer, "{}", cursor::Goto(x, y + row as u16))?;
-            let mut index = 0;
-            // for better padding
-            write!(writer, " ")?;
-            taken.insert((x + index, y + row as u16));
-            index += 1;
-            for digit in self.digits.char_indices() {
-                let chrs = (match digit.1 {
-                    '1' => Digit::one(),
-                    '2' => Digit::two(),
-                    '3' => Digit::three(),
-                    '4' => Digit::four(),
-                    '5' => Digit::five(),
-                    '6' => Digit::six(),
-                    '7' => Digit::seven(),
-                    '8' => Digit::eight(),
-                    '9' => Digit::nine(),
-                    '0' => Digit::zero(),
-                    ':' => Digit::colon(),
-                    'P' => Digit::p(),
-                    'A' => Digit::a(),
-                    'M' => Digit::m(),
-                    ' ' => Digit::space(),
-                    _ => panic!("Bad Number input"),
-                }
-                .1)
-                    .inner()[row];
-                for _ in 0..chrs.len() {
-                    taken.insert((x + index, y + row as u16));
-                    index += 1;
-                }
-                write!(writer, "{}", chrs)?;
+    fn draw(&self, writer: &mut dyn Write, x: u16, y: u16) -> std::io::Result<Vec<Rect>> {
+        write!(writer, "{}", self.style.on())?;
+        for (row, chrs) in self.rows.iter().enumerate() {
+            let y0 = y + row as u16;
+            write!(
+                writer,
+                "{goto}{chrs:<width$.width$}",
+                goto = cursor::Goto(x, y0),
+                chrs = chrs,
+                width = self.size.width as usize
+            )?;
+        }
+        write!(writer, "{}", self.style.off())?;
+        Ok(vec![Rect::covering(x, y, self.size)])
+    }
+    fn glyphs(&self, x: u16, y: u16) -> Vec<Rect> {
+        let width = self.size.width as usize;
+        (self.rows.iter().enumerate())
+            .flat_map(|(row, chrs)| {
+                let shown: String = chrs.chars().take(width).collect();
+                mask::runs(x, y + row as u16, &shown)
+            })
+            .collect()
+    }
+}
 
-                write!(writer, " ")?;
-                taken.insert((x + index, y + row as u16));
-                index += 1;
-            }
-            let fix_index = index;
-            for _ in 0..(self.size.width - fix_index as u16) {
-                write!(writer, " ")?;
-                taken.insert((x + index, y + row as u16));
-                index += 1;
-            }
+/// The lines a `Frame` is drawn with.
+#[derive(Copy, Clone)]
+pub enum Border {
+    /// `┌─┐`, thin lines.
+    Single,
+    /// `╔═╗`, double lines.
+    Double,
+    /// `╭─╮`, thin lines with round corners.
+    Rounded,
+    /// `┏━┓`, thick lines.
+    Heavy,
+    /// `+-+`, for terminals without box drawing characters.
+    Ascii,
+    /// The same character all the way around.
+    Solid(char),
+}
+
+impl Border {
+    // horizontal, vertical, then the corners clockwise from the top left
+    fn glyphs(self) -> [char; 6] {
+        match self {
+            Border::Single => ['─', '│', '┌', '┐', '┘', '└'],
+            Border::Double => ['═', '║', '╔', '╗', '╝', '╚'],
+            Border::Rounded => ['─', '│', '╭', '╮', '╯', '╰'],
+            Border::Heavy => ['━', '┃', '┏', '┓', '┛', '┗'],
+            Border::Ascii => ['-', '|', '+', '+', '+', '+'],
+            Border::Solid(c) => [c; 6],
         }
-        Ok(taken)
     }
 }
 
+/// A border around another widget, with an optional title.
 pub struct Frame {
     inner: Box<dyn Draw>,
-    material: char,
+    border: Border,
+    title: Option<String>,
+    style: Style,
 }
 
 impl Draw for Frame {
@@ -151,524 +301,379 @@ impl Draw for Frame {
             width: inner.width + 2,
         }
     }
-    fn draw(&self, writer: &mut dyn Write, x: u16, y: u16) -> std::io::Result<HashSet<(u16, u16)>> {
-        let mut taken = HashSet::new();
+    fn draw(&self, writer: &mut dyn Write, x: u16, y: u16) -> std::io::Result<Vec<Rect>> {
         let size = self.size();
-        write!(writer, "{}", cursor::Goto(x, y))?;
-        for i in 0..size.width - 2 {
-            write!(writer, "{}", self.material)?;
-            taken.insert((x + i, y));
+        let [line, side, top_left, top_right, bottom_right, bottom_left] = self.border.glyphs();
+        let inside = size.width as usize - 2;
+        let mut top = line.to_string().repeat(inside);
+        if let Some(title) = &self.title {
+            // one border character and a space on either side
+            if inside >= 5 {
+                let mut shown = String::new();
+                let mut width = 0;
+                for c in title.chars() {
+                    width += c.width().unwrap_or(0);
+                    if width > inside - 4 {
+                        break;
+                    }
+                    shown.push(c);
+                }
+                top = format!(
+                    "{line} {title} {rest}",
+                    line = line,
+                    title = shown,
+                    rest = line.to_string().repeat(inside - 3 - shown.width()),
+                );
+            }
         }
-        for y0 in y..y + size.height {
+        write!(writer, "{}", self.style.on())?;
+        write!(
+            writer,
+            "{}{}{}{}",
+            cursor::Goto(x, y),
+            top_left,
+            top,
+            top_right
+        )?;
+        for y0 in y + 1..y + size.height {
             write!(
                 writer,
-                "{g1}{m}{g2}{m}",
-                g1 = cursor::Goto(x, y0),
-                g2 = cursor::Goto(x + size.width - 2, y0),
-                m = self.material
+                "{}{}{}{}",
+                cursor::Goto(x, y0),
+                side,
+                cursor::Goto(x + size.width - 1, y0),
+                side
             )?;
-            taken.insert((x, y0));
-            taken.insert((x + size.width - 2, y0));
         }
-        write!(writer, "{}", cursor::Goto(x, y + size.height))?;
-        for i in 0..self.size().width - 1 {
-            write!(writer, "{}", self.material)?;
-            taken.insert((x + i, y + size.height));
-        }
-        let inner_hitbox = self.inner.draw(writer, x + 1, y + 1)?;
-        taken.extend(inner_hitbox);
-        Ok(taken)
+        write!(
+            writer,
+            "{}{}{}{}",
+            cursor::Goto(x, y + size.height),
+            bottom_left,
+            line.to_string().repeat(inside),
+            bottom_right
+        )?;
+        // only the background carries on inside, as a panel behind the contents
+        let border = Style {
+            bg: None,
+            ..self.style
+        };
+        write!(writer, "{}", border.off())?;
+        // everything inside is covered by the frame's own rect
+        self.inner.draw(writer, x + 1, y + 1)?;
+        write!(writer, "{}", self.style.off())?;
+        Ok(vec![Rect::covering(x, y, size)])
+    }
+    fn glyphs(&self, x: u16, y: u16) -> Vec<Rect> {
+        let size = self.size();
+        // rain would punch holes in a panel, it's drawn on the terminal's background
+        if self.style.bg.is_some() {
+            return vec![Rect::covering(x, y, size)];
+        }
+        let mut glyphs = self.inner.glyphs(x + 1, y + 1);
+        // a border of spaces is only there to keep the rain at a distance
+        if let Border::Solid(' ') = self.border {
+            return glyphs;
+        }
+        glyphs.push(Rect::new(x, y, size.width, 1));
+        glyphs.push(Rect::new(x, y + size.height, size.width, 1));
+        glyphs.push(Rect::new(x, y + 1, 1, size.height - 1));
+        glyphs.push(Rect::new(x + size.width - 1, y + 1, 1, size.height - 1));
+        glyphs
     }
 }
 
 impl Frame {
+    /// `contents` surrounded by `material`.
     pub fn from(material: char, contents: Box<dyn Draw>) -> Self {
         Self {
             inner: contents,
-            material: material,
+            border: Border::Solid(material),
+            title: None,
+            style: Style::default(),
         }
     }
+
+    /// The lines it's drawn with, instead of the same character all around.
+    pub fn with_border(self, border: Border) -> Self { Self { border, ..self } }
+
+    /// Text set into the top border, cut short if the frame is too narrow.
+    pub fn with_title(self, title: &str) -> Self {
+        let title = title.chars().filter(|c| !c.is_control()).collect();
+        Self {
+            title: Some(title),
+            ..self
+        }
+    }
+
+    /// The border is drawn in `style`, its background fills the inside too.
+    pub fn with_style(self, style: Style) -> Self { Self { style, ..self } }
 }
 
+/// Which side lines of text line up on.
+#[derive(Copy, Clone)]
+pub enum Align {
+    /// Flush with the left edge.
+    Left,
+    /// Centered between the edges.
+    Center,
+    /// Flush with the right edge.
+    Right,
+}
+
+/// A block of text, wrapped and aligned.
 pub struct Label {
-    lines: Vec<String>,
-    size: Size,
+    lines: Vec<Vec<Span>>,
+    // display width of the longest line
+    width: u16,
+    align: Align,
+    // blank columns on each side, blank rows above and below
+    padding: (u16, u16),
+    style: Style,
 }
 
 impl Label {
-    pub fn from(contents: &str) -> Self {
-        let mut size = Size {
+    /// Plain text, escape sequences and control characters are dropped.
+    pub fn from(contents: &str) -> Self { Self::parsed(text::parse(contents, false)) }
+
+    /// Like `from`, but keeps the colors set by SGR escape sequences.
+    pub fn styled(contents: &str) -> Self { Self::parsed(text::parse(contents, true)) }
+
+    /// Word wraps lines longer than `width` columns, not counting padding.
+    pub fn with_max_width(self, width: u16) -> Self {
+        Self {
+            lines: text::wrap(self.lines, width as usize),
+            ..self
+        }
+        .measured()
+    }
+
+    /// How shorter lines sit next to the longest one.
+    pub fn with_align(self, align: Align) -> Self { Self { align, ..self } }
+
+    /// Blank space around the text, `x` columns on each side and `y` rows
+    /// above and below.
+    pub fn with_padding(self, x: u16, y: u16) -> Self {
+        Self {
+            padding: (x, y),
+            ..self
+        }
+    }
+
+    /// Colors and attributes for the text. Spans with their own colors keep them.
+    pub fn with_style(self, style: Style) -> Self { Self { style, ..self } }
+
+    fn parsed(lines: Vec<Vec<Span>>) -> Self {
+        Self {
+            lines,
             width: 0,
-            height: 0,
+            align: Align::Left,
+            padding: (0, 0),
+            style: Style::default(),
+        }
+        .measured()
+    }
+
+    // columns of blank space before `line`
+    fn indent(&self, line: &[Span]) -> usize {
+        let free = (self.width as usize).saturating_sub(text::width(line));
+        let offset = match self.align {
+            Align::Left => 0,
+            Align::Center => free / 2,
+            Align::Right => free,
         };
-        let lines = contents
-            .lines()
-            .map(|x| {
-                size.height += 1;
-                if size.width < x.len() as u16 + 1 {
-                    size.width = x.len() as u16 + 1;
-                }
+        offset + self.padding.0 as usize
+    }
 
-                x.to_owned()
-            })
-            .collect();
-        size.height -= 1;
+    fn measured(self) -> Self {
+        let width = self.lines.iter().map(|l| text::width(l)).max().unwrap_or(0);
         Self {
-            lines: lines,
-            size: size,
+            width: width as u16,
+            ..self
         }
     }
 }
 
 impl Draw for Label {
-    fn size(&self) -> Size { self.size }
-    fn draw(&self, writer: &mut dyn Write, x: u16, y: u16) -> std::io::Result<HashSet<(u16, u16)>> {
-        for i in 0..self.lines.len() {
+    fn size(&self) -> Size {
+        let (x, y) = self.padding;
+        Size::from(self.lines.len() as u16 - 1 + 2 * y, self.width + 2 * x)
+    }
+    fn draw(&self, writer: &mut dyn Write, x: u16, y: u16) -> std::io::Result<Vec<Rect>> {
+        let size = self.size();
+        let blank = " ".repeat(size.width as usize);
+        write!(writer, "{}", self.style.on())?;
+        for y0 in 0..self.padding.1 {
+            write!(writer, "{}{}", cursor::Goto(x, y + y0), blank)?;
+            write!(writer, "{}{}", cursor::Goto(x, y + size.height - y0), blank)?;
+        }
+        for (i, line) in self.lines.iter().enumerate() {
+            let free = (self.width as usize).saturating_sub(text::width(line));
+            let before = self.indent(line);
+            let after = free + 2 * self.padding.0 as usize - before;
             write!(
                 writer,
-                "{goto}{line}",
-                goto = cursor::Goto(x, y + i as u16),
-                line = self.lines[i]
+                "{}{:before$}",
+                cursor::Goto(x, y + self.padding.1 + i as u16),
+                "",
+                before = before
             )?;
+            for span in line {
+                if span.style.is_empty() {
+                    write!(writer, "{}", span.text)?;
+                } else {
+                    // the colors from the text replace ours until it's done
+                    let (on, off) = (span.style.on(), span.style.off());
+                    write!(writer, "{}{}{}", on, span.text, off)?;
+                    write!(writer, "{}", self.style.on())?;
+                }
+            }
+            write!(writer, "{:after$}", "", after = after)?;
         }
-        Ok(dense_hitbox(x, self.size.width(), y, self.size.height()))
+        write!(writer, "{}", self.style.off())?;
+        Ok(vec![Rect::covering(x, y, size)])
     }
-}
-
-fn dense_hitbox(x0: u16, x_len: u16, y0: u16, y_len: u16) -> HashSet<(u16, u16)> {
-    let mut taken = HashSet::<(u16, u16)>::new();
-    for x in x0..x0 + x_len {
-        for y in y0..y0 + y_len + 1 {
-            taken.insert((x, y));
-        }
+    fn glyphs(&self, x: u16, y: u16) -> Vec<Rect> {
+        (self.lines.iter().enumerate())
+            .flat_map(|(i, line)| {
+                let text: String = line.iter().map(|s| s.text.as_str()).collect();
+                let x0 = x + self.indent(line) as u16;
+                mask::runs(x0, y + self.padding.1 + i as u16, &text)
+            })
+            .collect()
     }
-    taken
 }
 
-pub struct Blank {
+/// A month laid out by week, like `cal`.
+pub struct Calendar {
+    lines: Vec<String>,
+    // row and column of today's number
+    today: (usize, usize),
     size: Size,
+    style: Style,
 }
 
-impl Blank {
-    pub fn from(mut size: Size) -> Self {
-        size.height += 1;
-        Self { size: size }
+impl Calendar {
+    /// The month `today` is in, with today highlighted.
+    pub fn from(today: NaiveDate) -> Self {
+        let first = today.with_day(1).unwrap_or(today);
+        let next = if today.month() == 12 {
+            NaiveDate::from_ymd_opt(today.year() + 1, 1, 1)
+        } else {
+            first.with_month(today.month() + 1)
+        };
+        // there's no next month after the last date chrono has
+        let days = next.map_or(31, |next| {
+            next.signed_duration_since(first).num_days() as u32
+        });
+        let title = first.format("%B %Y").to_string();
+        let mut lines = vec![format!("{:^20}", title), "Mo Tu We Th Fr Sa Su".to_owned()];
+        let mut week = "   ".repeat(first.weekday().num_days_from_monday() as usize);
+        let mut position = (0, 0);
+        for day in 1..=days {
+            if day == today.day() {
+                position = (lines.len(), week.len());
+            }
+            week.push_str(&format!("{:>2} ", day));
+            if week.len() == 21 {
+                lines.push(format!("{:20}", week.trim_end()));
+                week.clear();
+            }
+        }
+        if !week.is_empty() {
+            lines.push(format!("{:20}", week.trim_end()));
+        }
+        Self {
+            size: Size::from(lines.len() as u16 - 1, 20),
+            lines,
+            today: position,
+            style: Style::default(),
+        }
     }
+
+    /// Colors and attributes for the days, today is shown in reverse.
+    pub fn with_style(self, style: Style) -> Self { Self { style, ..self } }
 }
 
-impl Draw for Blank {
+impl Draw for Calendar {
     fn size(&self) -> Size { self.size }
-    fn draw(&self, writer: &mut dyn Write, x: u16, y: u16) -> std::io::Result<HashSet<(u16, u16)>> {
-        for y0 in y..y + self.size.height() {
-            write!(writer, "{}", cursor::Goto(x, y0))?;
-            for _ in x..x + self.size.width() {
-                write!(writer, " ")?;
+    fn draw(&self, writer: &mut dyn Write, x: u16, y: u16) -> std::io::Result<Vec<Rect>> {
+        write!(writer, "{}", self.style.on())?;
+        for (i, line) in self.lines.iter().enumerate() {
+            write!(writer, "{}", cursor::Goto(x, y + i as u16))?;
+            if i == self.today.0 {
+                let (before, rest) = line.split_at(self.today.1);
+                let (day, after) = rest.split_at(2);
+                write!(
+                    writer,
+                    "{}{}{}{}{}",
+                    before,
+                    style::Invert,
+                    day,
+                    style::NoInvert,
+                    after
+                )?;
+            } else {
+                write!(writer, "{}", line)?;
             }
         }
-        Ok(dense_hitbox(x, self.size.width(), y, self.size.height()))
+        write!(writer, "{}", self.style.off())?;
+        Ok(vec![Rect::covering(x, y, self.size)])
+    }
+    fn glyphs(&self, x: u16, y: u16) -> Vec<Rect> {
+        (self.lines.iter().enumerate())
+            .flat_map(|(i, line)| mask::runs(x, y + i as u16, line))
+            .collect()
     }
 }
 
-mod fonts {
-
-    use super::Size;
-    pub trait Font {
-        fn one() -> (Size, Self);
-        fn two() -> (Size, Self);
-        fn three() -> (Size, Self);
-        fn four() -> (Size, Self);
-        fn five() -> (Size, Self);
-        fn six() -> (Size, Self);
-        fn seven() -> (Size, Self);
-        fn eight() -> (Size, Self);
-        fn nine() -> (Size, Self);
-        fn zero() -> (Size, Self);
-        fn a() -> (Size, Self);
-        fn p() -> (Size, Self);
-        fn m() -> (Size, Self);
-        fn colon() -> (Size, Self);
-        fn space() -> (Size, Self);
-        fn inner(&self) -> &Vec<&'static str>;
-        fn clock_size(&self) -> u16;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anchors_keep_their_margin() {
+        let (size, screen) = (Size::new(3, 10), (80, 24));
+        assert_eq!(Anchor::TopLeft.place(size, (2, 1), screen), Some((3, 2)));
+        assert_eq!(
+            Anchor::BottomRight.place(size, (2, 1), screen),
+            Some((69, 21))
+        );
+        assert_eq!(Anchor::Center.place(size, (0, 0), screen), Some((36, 11)));
+        // centered axes are shifted by it instead
+        assert_eq!(Anchor::Center.place(size, (5, 2), screen), Some((41, 13)));
     }
 
-    pub struct Doom(Vec<&'static str>);
-    impl Font for Doom {
-        fn inner(&self) -> &Vec<&'static str> { &self.0 }
-        fn one() -> (Size, Self) {
-            (
-                Size::from(6, 6),
-                Self(vec![
-                    r" __  ", r"/  | ", r"`| | ", r" | | ", r"_| |_", r"\___/",
-                ]),
-            )
-        }
-        fn two() -> (Size, Self) {
-            (
-                Size::from(6, 7),
-                Self(vec![
-                    r" _____ ", r"/ __  \", r"`' / /'", r"  / /  ", r"./ /___", r"\_____/",
-                ]),
-            )
-        }
-        fn three() -> (Size, Self) {
-            (
-                Size::from(6, 7),
-                Self(vec![
-                    r" _____ ", r"|____ |", r"    / /", r"    \ \", r".___/ /", r"\____/ ",
-                ]),
-            )
-        }
-        fn four() -> (Size, Self) {
-            (
-                Size::from(6, 7),
-                Self(vec![
-                    r"   ___ ", r"  /   |", r" / /| |", r"/ /_| |", r"\___  |", r"    |_/",
-                ]),
-            )
-        }
-        fn five() -> (Size, Self) {
-            (
-                Size::from(6, 7),
-                Self(vec![
-                    r" _____ ", r"|  ___|", r"|___ \ ", r"    \ \", r"/\__/ /", r"\____/ ",
-                ]),
-            )
-        }
-        fn six() -> (Size, Self) {
-            (
-                Size::from(6, 7),
-                Self(vec![
-                    r"  ____ ", r" / ___|", r"/ /___ ", r"| ___ \", r"| \_/ |", r"\_____/",
-                ]),
-            )
-        }
-        fn seven() -> (Size, Self) {
-            (
-                Size::from(6, 7),
-                Self(vec![
-                    r" ______", r"|___  /", r"   / / ", r"  / /  ", r"./ /   ", r"\_/    ",
-                ]),
-            )
-        }
-        fn eight() -> (Size, Self) {
-            (
-                Size::from(6, 7),
-                Self(vec![
-                    r" _____ ", r"|  _  |", r" \ V / ", r" / _ \ ", r"| |_| |", r"\_____/",
-                ]),
-            )
-        }
-        fn nine() -> (Size, Self) {
-            (
-                Size::from(6, 6),
-                Self(vec![
-                    r" _____ ", r"|  _  |", r"| |_| |", r"\____ |", r".___/ /", r"\____/ ",
-                ]),
-            )
-        }
-        fn zero() -> (Size, Self) {
-            (
-                Size::from(6, 7),
-                Self(vec![
-                    r" _____ ", r"|  _  |", r"| |/' |", r"|  /| |", r"\ |_/ /", r" \___/ ",
-                ]),
-            )
-        }
-        fn colon() -> (Size, Self) {
-            (
-                Size::from(6, 3),
-                Self(vec![r"   ", r" _ ", r"(_)", r"   ", r" _ ", r"(_)"]),
-            )
-        }
-        fn a() -> (Size, Self) {
-            (
-                Size::from(6, 7),
-                Self(vec![
-                    r"  ___  ", r" / _ \ ", r"/ /_\ \", r"|  _  |", r"| | | |", r"\_| |_/",
-                ]),
-            )
-        }
-        fn p() -> (Size, Self) {
-            (
-                Size::from(6, 8),
-                Self(vec![
-                    r"______ ", r"| ___ \", r"| |_/ /", r"|  __/ ", r"| |    ", r"\_|    ",
-                ]),
-            )
-        }
-        fn m() -> (Size, Self) {
-            (
-                Size::from(6, 8),
-                Self(vec![
-                    r"___  ___",
-                    r"|  \/  |",
-                    r"| .  . |",
-                    r"| |\/| |",
-                    r"| |  | |",
-                    r"\_|  |_/",
-                ]),
-            )
-        }
-        fn space() -> (Size, Self) {
-            (
-                Size::from(6, 1),
-                Self(vec![r" ", r" ", r" ", r" ", r" ", r" "]),
-            )
-        }
-        fn clock_size(&self) -> u16 { 75 }
+    #[test]
+    fn margins_are_clamped_to_the_screen() {
+        let (size, screen) = (Size::new(3, 10), (80, 24));
+        assert_eq!(
+            Anchor::TopLeft.place(size, (100, 100), screen),
+            Some((71, 22))
+        );
+        assert_eq!(
+            Anchor::BottomRight.place(size, (100, 100), screen),
+            Some((1, 1))
+        );
+        assert_eq!(
+            Anchor::Center.place(size, (100, 100), screen),
+            Some((71, 22))
+        );
     }
 
-    pub struct Colossal(Vec<&'static str>);
-    impl Font for Colossal {
-        fn inner(&self) -> &Vec<&'static str> { &self.0 }
-        fn clock_size(&self) -> u16 { 104 }
-        // Colossal font: "https://onlineasciitools.com/convert-text-to-ascii-art"
-        fn one() -> (Size, Self) {
-            (
-                Size {
-                    height: 8,
-                    width: 8,
-                },
-                Self(vec![
-                    " d888  ", "d8888  ", "  888  ", "  888  ", "  888  ", "  888  ", "  888  ",
-                    "8888888",
-                ]),
-            )
-        }
-        fn two() -> (Size, Self) {
-            (
-                Size {
-                    height: 8,
-                    width: 11,
-                },
-                Self(vec![
-                    " .d8888b.  ",
-                    "d88P  Y88b ",
-                    "       888 ",
-                    "     .d88P ",
-                    " .od888P\"  ",
-                    "d88P\"      ",
-                    "888\"       ",
-                    "888888888  ",
-                ]),
-            )
-        }
-        fn three() -> (Size, Self) {
-            (
-                Size {
-                    height: 8,
-                    width: 10,
-                },
-                Self(vec![
-                    " .d8888b. ",
-                    "d88P  Y88b",
-                    "     .d88P",
-                    "    8888\" ",
-                    "     \"Y8b.",
-                    "888    888",
-                    "Y88b  d88P",
-                    " \"Y8888P\" ",
-                ]),
-            )
-        }
-        fn four() -> (Size, Self) {
-            (
-                Size {
-                    height: 8,
-                    width: 10,
-                },
-                Self(vec![
-                    "    d8888 ",
-                    "   d8P888 ",
-                    "  d8P 888 ",
-                    " d8P  888 ",
-                    "d88   888 ",
-                    "8888888888",
-                    "      888 ",
-                    "      888 ",
-                ]),
-            )
-        }
-        fn five() -> (Size, Self) {
-            (
-                Size {
-                    height: 8,
-                    width: 10,
-                },
-                Self(vec![
-                    "888888888 ",
-                    "888       ",
-                    "888       ",
-                    "8888888b. ",
-                    "     \"Y88b",
-                    "       888",
-                    "Y88b  d88P",
-                    " \"Y8888P\" ",
-                ]),
-            )
-        }
-        fn six() -> (Size, Self) {
-            (
-                Size {
-                    height: 8,
-                    width: 10,
-                },
-                Self(vec![
-                    " .d8888b. ",
-                    "d88P  Y88b",
-                    "888       ",
-                    "888d888b. ",
-                    "888P \"Y88b",
-                    "888    888",
-                    "Y88b  d88P",
-                    " \"Y8888P\" ",
-                ]),
-            )
-        }
-        fn seven() -> (Size, Self) {
-            (
-                Size {
-                    height: 8,
-                    width: 10,
-                },
-                Self(vec![
-                    "8888888888",
-                    "      d88P",
-                    "     d88P ",
-                    "    d88P  ",
-                    " 88888888 ",
-                    "  d88P    ",
-                    " d88P     ",
-                    "d88P      ",
-                ]),
-            )
-        }
-        fn eight() -> (Size, Self) {
-            (
-                Size {
-                    height: 8,
-                    width: 11,
-                },
-                Self(vec![
-                    " .d8888b.  ",
-                    "d88P  Y88b ",
-                    "Y88b. d88P ",
-                    " \"Y88888\"  ",
-                    ".d8P\"\"Y8b. ",
-                    "888    888 ",
-                    "Y88b  d88P ",
-                    " \"Y8888P\"  ",
-                ]),
-            )
-        }
-        fn nine() -> (Size, Self) {
-            (
-                Size {
-                    height: 8,
-                    width: 10,
-                },
-                Self(vec![
-                    " .d8888b. ",
-                    "d88P  Y88b",
-                    "888    888",
-                    "Y88b. d888",
-                    " \"Y888P888",
-                    "       888",
-                    "Y88b  d88P",
-                    " \"Y8888P\" ",
-                ]),
-            )
-        }
-        fn zero() -> (Size, Self) {
-            (
-                Size {
-                    height: 8,
-                    width: 11,
-                },
-                Self(vec![
-                    " .d8888b. ",
-                    "d88P  Y88b",
-                    "888    888",
-                    "888    888",
-                    "888    888",
-                    "888    888",
-                    "Y88b  d88P",
-                    " \"Y8888P\" ",
-                ]),
-            )
-        }
-        fn colon() -> (Size, Self) {
-            (
-                Size {
-                    height: 8,
-                    width: 3,
-                },
-                Self(vec!["   ", "d8b", "Y8P", "   ", "   ", "d8b", "Y8P", "   "]),
-            )
-        }
-        fn a() -> (Size, Self) {
-            (
-                Size {
-                    height: 8,
-                    width: 12,
-                },
-                Self(vec![
-                    "       d8888",
-                    "      d88888",
-                    "     d88P888",
-                    "    d88P 888",
-                    "   d88P  888",
-                    "  d88P   888",
-                    " d8888888888",
-                    "d88P     888",
-                ]),
-            )
-        }
-        fn p() -> (Size, Self) {
-            (
-                Size {
-                    height: 8,
-                    width: 10,
-                },
-                Self(vec![
-                    "8888888b. ",
-                    "888   Y88b",
-                    "888    888",
-                    "888   d88P",
-                    "8888888P\" ",
-                    "888       ",
-                    "888       ",
-                    "888       ",
-                ]),
-            )
-        }
-        fn m() -> (Size, Self) {
-            (
-                Size {
-                    height: 8,
-                    width: 13,
-                },
-                Self(vec![
-                    "888b     d888",
-                    "8888b   d8888",
-                    "88888b.d88888",
-                    "888Y88888P888",
-                    "888 Y888P 888",
-                    "888  Y8P  888",
-                    "888   \"   888",
-                    "888       888",
-                ]),
-            )
-        }
-        fn space() -> (Size, Self) {
-            (
-                Size {
-                    height: 8,
-                    width: 2,
-                },
-                Self(vec!["  ", "  ", "  ", "  ", "  ", "  ", "  ", "  "]),
-            )
-        }
+    #[test]
+    fn anchors_need_room() {
+        assert_eq!(
+            Anchor::Center.place(Size::new(3, 10), (0, 0), (10, 3)),
+            Some((1, 1))
+        );
+        assert_eq!(
+            Anchor::Center.place(Size::new(3, 11), (0, 0), (10, 3)),
+            None
+        );
+        assert_eq!(
+            Anchor::Center.place(Size::new(4, 10), (0, 0), (10, 3)),
+            None
+        );
     }
 }
@@ -0,0 +1,70 @@
+use super::{Draw, Rect, Size};
+use std::io::Write;
+
+/// Holds on to a widget between frames. It's only rebuilt when its content
+/// changes and only redrawn when it's rebuilt or moved, since nothing else
+/// draws over it.
+#[derive(Default)]
+pub struct Cached {
+    // what the widget was built from
+    key: String,
+    widget: Option<Box<dyn Draw>>,
+    drawn: Option<Drawn>,
+}
+
+// where a widget was last drawn, what that covered and where its glyphs went
+struct Drawn {
+    at: (u16, u16),
+    area: Vec<Rect>,
+    glyphs: Vec<Rect>,
+}
+
+impl Cached {
+    /// Nothing to draw until `update` builds something.
+    pub fn new() -> Self { Self::default() }
+
+    /// Rebuilds the widget with `build` if `key` isn't what it was built from.
+    pub fn update(&mut self, key: &str, build: impl FnOnce() -> Box<dyn Draw>) {
+        if self.widget.is_none() || self.key != key {
+            self.key = key.to_owned();
+            self.widget = Some(build());
+            self.drawn = None;
+        }
+    }
+
+    /// How much room the widget needs, `None` before it's built.
+    pub fn size(&self) -> Option<Size> { self.widget.as_ref().map(|w| w.size()) }
+
+    /// Draws the widget at `at` if it isn't there already. `None` hides it.
+    /// Returns whether anything was drawn.
+    pub fn draw(
+        &mut self,
+        writer: &mut dyn Write,
+        at: Option<(u16, u16)>,
+    ) -> std::io::Result<bool> {
+        let (widget, at) = match (&self.widget, at) {
+            (Some(widget), Some(at)) => (widget, at),
+            _ => {
+                self.drawn = None;
+                return Ok(false);
+            }
+        };
+        match &self.drawn {
+            Some(drawn) if drawn.at == at => Ok(false),
+            _ => {
+                self.drawn = Some(Drawn {
+                    at,
+                    area: widget.draw(writer, at.0, at.1)?,
+                    glyphs: widget.glyphs(at.0, at.1),
+                });
+                Ok(true)
+            }
+        }
+    }
+
+    /// What the widget covers where it's drawn, nothing while it's hidden.
+    pub fn area(&self) -> &[Rect] { self.drawn.as_ref().map_or(&[], |d| &d.area) }
+
+    /// The cells of `area` that have something in them.
+    pub fn glyphs(&self) -> &[Rect] { self.drawn.as_ref().map_or(&[], |d| &d.glyphs) }
+}
@@ -0,0 +1,363 @@
+// Loader for FIGlet `.flf` font files.
+// Format reference: "http://www.jave.de/figlet/figfont.html"
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+
+use super::Font;
+
+const SMUSH_EQUAL: i32 = 1;
+const SMUSH_LOWLINE: i32 = 2;
+const SMUSH_HIERARCHY: i32 = 4;
+const SMUSH_PAIR: i32 = 8;
+const SMUSH_BIGX: i32 = 16;
+const SMUSH_HARDBLANK: i32 = 32;
+const SMUSH_KERN: i32 = 64;
+const SMUSH_SMUSH: i32 = 128;
+
+// the characters every font must define after ASCII, in this order
+const DEUTSCH: [u32; 7] = [196, 214, 220, 228, 246, 252, 223];
+
+/// A font read from a FIGlet `.flf` file.
+pub struct FigletFont {
+    height: usize,
+    hardblank: char,
+    layout: i32,
+    glyphs: HashMap<char, Vec<String>>,
+}
+
+impl FigletFont {
+    /// Reads the font at `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Reads a font from the contents of a `.flf` file.
+    pub fn parse(contents: &str) -> std::io::Result<Self> {
+        let mut lines = contents.lines();
+        let header = lines.next().ok_or_else(|| bad_font("empty file"))?;
+        if !header.starts_with("flf2a") {
+            return Err(bad_font("missing flf2a signature"));
+        }
+        let hardblank = header[5..]
+            .chars()
+            .next()
+            .ok_or_else(|| bad_font("missing hardblank"))?;
+        let params = header[5 + hardblank.len_utf8()..]
+            .split_whitespace()
+            .map(|p| p.parse::<i32>().map_err(|_| bad_font("bad header")))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        if params.len() < 5 || params[0] < 1 {
+            return Err(bad_font("bad header"));
+        }
+        let height = params[0] as usize;
+        let old_layout = params[3];
+        let layout = match params.get(6) {
+            Some(full) => *full,
+            None if old_layout == -1 => 0,
+            None if old_layout == 0 => SMUSH_KERN,
+            None => (old_layout & 63) | SMUSH_SMUSH,
+        };
+        for _ in 0..params[4] {
+            lines.next();
+        }
+
+        let mut glyphs = HashMap::new();
+        let required = (32..127).chain(DEUTSCH.iter().cloned());
+        for code in required {
+            match read_glyph(&mut lines, height)? {
+                Some(rows) => {
+                    if let Some(c) = std::char::from_u32(code) {
+                        glyphs.insert(c, rows);
+                    }
+                }
+                // some old fonts stop after the ASCII characters
+                None if code > 126 => break,
+                None => return Err(bad_font("missing required characters")),
+            }
+        }
+        // code tagged characters run until the end of the file
+        while let Some(tag) = lines.next() {
+            if tag.trim().is_empty() {
+                continue;
+            }
+            let code = tag
+                .split_whitespace()
+                .next()
+                .and_then(parse_code)
+                .ok_or_else(|| bad_font("bad code tag"))?;
+            let rows =
+                read_glyph(&mut lines, height)?.ok_or_else(|| bad_font("truncated glyph"))?;
+            // negative codes are reserved for font-specific use and can't be typed
+            if let Some(c) = u32::try_from(code).ok().and_then(std::char::from_u32) {
+                glyphs.insert(c, rows);
+            }
+        }
+
+        Ok(Self {
+            height,
+            hardblank,
+            layout,
+            glyphs,
+        })
+    }
+
+    // How many columns the next glyph may slide into the current output.
+    fn smush_amount(
+        &self,
+        out: &[Vec<char>],
+        glyph: &[Vec<char>],
+        prev_width: usize,
+        width: usize,
+    ) -> usize {
+        if self.layout & (SMUSH_KERN | SMUSH_SMUSH) == 0 {
+            return 0;
+        }
+        let mut max = width;
+        for (line, row) in out.iter().zip(glyph) {
+            let line_end = line.iter().rposition(|&c| c != ' ');
+            let row_start = row.iter().position(|&c| c != ' ').unwrap_or(row.len());
+            let mut amount = row_start
+                + match line_end {
+                    Some(i) => line.len() - 1 - i,
+                    None => line.len(),
+                };
+            if let (Some(i), Some(&ch)) = (line_end, row.get(row_start)) {
+                if self.smush(line[i], ch, prev_width, width).is_some() {
+                    amount += 1;
+                }
+            }
+            if amount < max {
+                max = amount;
+            }
+        }
+        max.min(out.iter().map(|l| l.len()).min().unwrap_or(0))
+    }
+
+    // Merges two overlapping characters, or `None` when they can't share a cell.
+    fn smush(&self, left: char, right: char, prev_width: usize, width: usize) -> Option<char> {
+        if left == ' ' {
+            return Some(right);
+        }
+        if right == ' ' {
+            return Some(left);
+        }
+        if prev_width < 2 || width < 2 || self.layout & SMUSH_SMUSH == 0 {
+            return None;
+        }
+        let rules = self.layout & 63;
+        if rules == 0 {
+            // universal smushing, the later character wins
+            return Some(if right == self.hardblank { left } else { right });
+        }
+        if left == self.hardblank || right == self.hardblank {
+            let both = left == right;
+            return if both && rules & SMUSH_HARDBLANK != 0 {
+                Some(left)
+            } else {
+                None
+            };
+        }
+        if rules & SMUSH_EQUAL != 0 && left == right {
+            return Some(left);
+        }
+        if rules & SMUSH_LOWLINE != 0 {
+            const REPLACERS: &str = "|/\\[]{}()<>";
+            if left == '_' && REPLACERS.contains(right) {
+                return Some(right);
+            }
+            if right == '_' && REPLACERS.contains(left) {
+                return Some(left);
+            }
+        }
+        if rules & SMUSH_HIERARCHY != 0 {
+            const CLASSES: [&str; 6] = ["|", "/\\", "[]", "{}", "()", "<>"];
+            let class = |c| CLASSES.iter().position(|k| k.contains(c));
+            if let (Some(l), Some(r)) = (class(left), class(right)) {
+                if l < r {
+                    return Some(right);
+                }
+                if r < l {
+                    return Some(left);
+                }
+            }
+        }
+        if rules & SMUSH_PAIR != 0 {
+            match (left, right) {
+                ('[', ']') | (']', '[') | ('{', '}') | ('}', '{') | ('(', ')') | (')', '(') => {
+                    return Some('|')
+                }
+                _ => {}
+            }
+        }
+        if rules & SMUSH_BIGX != 0 {
+            match (left, right) {
+                ('/', '\\') => return Some('|'),
+                ('\\', '/') => return Some('Y'),
+                ('>', '<') => return Some('X'),
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+impl Font for FigletFont {
+    fn height(&self) -> usize { self.height }
+    fn glyph(&self, c: char) -> Option<Vec<&str>> {
+        self.glyphs
+            .get(&c)
+            .map(|rows| rows.iter().map(|r| r.as_str()).collect())
+    }
+    /// Merges neighbouring glyphs as far as the font's layout mode allows.
+    /// Hardblanks come out as spaces.
+    fn render(&self, text: &str) -> Vec<String> {
+        let mut out: Vec<Vec<char>> = vec![Vec::new(); self.height];
+        let mut prev_width = 0;
+        for c in text.chars() {
+            let glyph: Vec<Vec<char>> = match self.glyphs.get(&c) {
+                Some(g) => g.iter().map(|r| r.chars().collect()).collect(),
+                None => continue,
+            };
+            let width = glyph.iter().map(|r| r.len()).max().unwrap_or(0);
+            let amount = self.smush_amount(&out, &glyph, prev_width, width);
+            for (line, row) in out.iter_mut().zip(&glyph) {
+                let start = line.len() - amount;
+                for (k, &ch) in row.iter().enumerate() {
+                    if k < amount {
+                        let left = line[start + k];
+                        line[start + k] = self.smush(left, ch, prev_width, width).unwrap_or(ch);
+                    } else {
+                        line.push(ch);
+                    }
+                }
+            }
+            prev_width = width;
+        }
+        out.into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|c| if c == self.hardblank { ' ' } else { c })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+fn read_glyph<'a, I: Iterator<Item = &'a str>>(
+    lines: &mut I,
+    height: usize,
+) -> std::io::Result<Option<Vec<String>>> {
+    let mut rows = Vec::with_capacity(height);
+    for i in 0..height {
+        let line = match lines.next() {
+            Some(l) => l,
+            None if i == 0 => return Ok(None),
+            None => return Err(bad_font("truncated glyph")),
+        };
+        // every row ends in an end mark, the last one in two, and the mark may
+        // be used in the art as well
+        let mut row = line;
+        if let Some(mark) = line.chars().last() {
+            row = &row[..row.len() - mark.len_utf8()];
+            if i == height - 1 {
+                row = row.strip_suffix(mark).unwrap_or(row);
+            }
+        }
+        rows.push(row.to_owned());
+    }
+    Ok(Some(rows))
+}
+
+fn parse_code(tag: &str) -> Option<i64> {
+    let (sign, tag) = match tag.strip_prefix('-') {
+        Some(t) => (-1, t),
+        None => (1, tag),
+    };
+    let code = if let Some(hex) = tag.strip_prefix("0x").or_else(|| tag.strip_prefix("0X")) {
+        i64::from_str_radix(hex, 16).ok()?
+    } else if tag.len() > 1 && tag.starts_with('0') {
+        i64::from_str_radix(&tag[1..], 8).ok()?
+    } else {
+        tag.parse().ok()?
+    };
+    Some(sign * code)
+}
+
+fn bad_font(reason: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("bad FIGlet font: {}", reason),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the required characters, all blank except for `glyphs`, and then `tagged`
+    fn font(header: &str, glyphs: &[(char, [&str; 2])], tagged: &str) -> String {
+        let mut flf = format!("{}\n", header);
+        for code in (32..127).chain(DEUTSCH.iter().cloned()) {
+            let c = std::char::from_u32(code).unwrap();
+            match glyphs.iter().find(|g| g.0 == c) {
+                Some((_, rows)) => flf += &format!("{}\n{}\n", rows[0], rows[1]),
+                None => flf += " @\n @@\n",
+            }
+        }
+        flf + tagged
+    }
+
+    #[test]
+    fn strips_one_end_mark_a_row() {
+        let flf = font("flf2a$ 2 1 8 -1 0", &[('A', ["/\\@@", "@@@@"])], "");
+        let font = FigletFont::parse(&flf).unwrap();
+        assert_eq!(font.glyph('A'), Some(vec!["/\\@", "@@"]));
+        assert_eq!(font.glyph(' '), Some(vec![" ", " "]));
+    }
+
+    #[test]
+    fn reads_code_tagged_characters() {
+        let tagged = "0x263A  SMILE\n:)#\n:(##\n-5 hidden\nx#\nx##\n";
+        let font = FigletFont::parse(&font("flf2a$ 2 1 8 -1 0", &[], tagged)).unwrap();
+        assert_eq!(font.glyph('\u{263A}'), Some(vec![":)", ":("]));
+        assert_eq!(font.glyphs.len(), 95 + 7 + 1);
+    }
+
+    #[test]
+    fn refuses_bad_files() {
+        assert!(FigletFont::parse("").is_err());
+        assert!(FigletFont::parse("flf2b$ 2 1 8 -1 0\n").is_err());
+        assert!(FigletFont::parse("flf2a$ 2 1 8 -1 0\n @\n").is_err());
+    }
+
+    #[test]
+    fn smushes_by_the_rules() {
+        let flf = font("flf2a$ 2 1 8 -1 0 0 143", &[], "");
+        let font = FigletFont::parse(&flf).unwrap();
+        assert_eq!(font.smush('|', '|', 2, 2), Some('|'));
+        assert_eq!(font.smush('_', '/', 2, 2), Some('/'));
+        assert_eq!(font.smush('/', '|', 2, 2), Some('/'));
+        assert_eq!(font.smush('|', '<', 2, 2), Some('<'));
+        assert_eq!(font.smush('[', ']', 2, 2), Some('|'));
+        assert_eq!(font.smush('/', '\\', 2, 2), None);
+        assert_eq!(font.smush('a', 'b', 2, 2), None);
+        // single column glyphs never smush
+        assert_eq!(font.smush('|', '|', 1, 2), None);
+    }
+
+    #[test]
+    fn kerns_and_smushes_glyphs_together() {
+        let glyphs = [('I', ["|@", "|@@"])];
+        let kern = FigletFont::parse(&font("flf2a$ 2 1 8 0 0", &glyphs, "")).unwrap();
+        assert_eq!(kern.render("II"), vec!["||", "||"]);
+        let wide = [('I', [" |@", " |@@"])];
+        let kern = FigletFont::parse(&font("flf2a$ 2 1 8 0 0", &wide, "")).unwrap();
+        assert_eq!(kern.render("II"), vec![" ||", " ||"]);
+        let smush = FigletFont::parse(&font("flf2a$ 2 1 8 -1 0 0 143", &wide, "")).unwrap();
+        assert_eq!(smush.render("II"), vec![" |", " |"]);
+        let full = FigletFont::parse(&font("flf2a$ 2 1 8 -1 0", &wide, "")).unwrap();
+        assert_eq!(full.render("II"), vec![" | |", " | |"]);
+    }
+}
@@ -0,0 +1,1094 @@
+/// Big letters made out of characters, for `Numbers`.
+pub trait Font {
+    /// Rows every glyph is padded to, descenders included.
+    fn height(&self) -> usize;
+    /// The rows of `c`, or `None` if the font doesn't have it.
+    fn glyph(&self, c: char) -> Option<Vec<&str>>;
+    /// Lays `text` out on one line, one blank column between glyphs.
+    /// Characters the font doesn't have are skipped.
+    fn render(&self, text: &str) -> Vec<String> {
+        let mut rows = vec![String::new(); self.height()];
+        for glyph in text.chars().filter_map(|c| self.glyph(c)) {
+            let width = glyph.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+            let first = rows.iter().all(|r| r.is_empty());
+            for (i, row) in rows.iter_mut().enumerate() {
+                if !first {
+                    row.push(' ');
+                }
+                let chrs = glyph.get(i).cloned().unwrap_or("");
+                row.push_str(&format!("{:<width$}", chrs, width = width));
+            }
+        }
+        rows
+    }
+    /// Smallest width that fits every time the clock can show.
+    fn clock_size(&self) -> u16 {
+        (0..10)
+            .flat_map(|d| {
+                let t = format!("{d}{d}:{d}{d}:{d}{d}", d = d);
+                vec![format!("{}AM", t), format!("{}PM", t)]
+            })
+            .flat_map(|t| self.render(&t))
+            .map(|row| row.chars().count() as u16 + 2)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// The default font, eight rows high.
+pub struct Doom;
+impl Font for Doom {
+    fn height(&self) -> usize { 8 }
+    fn glyph(&self, c: char) -> Option<Vec<&str>> {
+        let rows: &[&str] = match c {
+            ' ' => &[r" ", r" ", r" ", r" ", r" ", r" "],
+            '!' => &[r" _ ", r"| |", r"| |", r"| |", r"|_|", r"(_)"],
+            '"' => &[r" _ _ ", r"( | )", r" V V "],
+            '#' => &[
+                r"   _  _   ",
+                r" _| || |_ ",
+                r"|_  __  _|",
+                r" _| || |_ ",
+                r"|_  __  _|",
+                r"  |_||_|  ",
+            ],
+            '$' => &[
+                r"  _   ", r" | |  ", r"/ __) ", r"\__ \ ", r"(   / ", r" |_|  ",
+            ],
+            '%' => &[
+                r" _   __", r"(_) / /", r"   / / ", r"  / /  ", r" / / _ ", r"/_/ (_)",
+            ],
+            '&' => &[
+                r"         ",
+                r"  ___    ",
+                r" ( _ )   ",
+                r" / _ \/\ ",
+                r"| (_>  < ",
+                r" \___/\/ ",
+            ],
+            '\'' => &[r" _ ", r"( )", r"|/ "],
+            '(' => &[
+                r"  __", r" / /", r"| | ", r"| | ", r"| | ", r"| | ", r" \_\",
+            ],
+            ')' => &[
+                r"__  ", r"\ \ ", r" | |", r" | |", r" | |", r" | |", r"/_/ ",
+            ],
+            '*' => &[
+                r"    _    ",
+                r" /\| |/\ ",
+                r" \ ` ' / ",
+                r"|_     _|",
+                r" / , . \ ",
+                r" \/|_|\/ ",
+            ],
+            '+' => &[r"       ", r"   _   ", r" _| |_ ", r"|_   _|", r"  |_|  "],
+            ',' => &[r"   ", r"   ", r"   ", r"   ", r" _ ", r"( )", r"|/ "],
+            '-' => &[r"        ", r"        ", r" ______ ", r"|______|"],
+            '.' => &[r"   ", r"   ", r"   ", r"   ", r" _ ", r"(_)"],
+            '/' => &[
+                r"     __", r"    / /", r"   / / ", r"  / /  ", r" / /   ", r"/_/    ",
+            ],
+            '0' => &[
+                r" _____ ", r"|  _  |", r"| |/' |", r"|  /| |", r"\ |_/ /", r" \___/ ",
+            ],
+            '1' => &[r" __  ", r"/  | ", r"`| | ", r" | | ", r"_| |_", r"\___/"],
+            '2' => &[
+                r" _____ ", r"/ __  \", r"`' / /'", r"  / /  ", r"./ /___", r"\_____/",
+            ],
+            '3' => &[
+                r" _____ ", r"|____ |", r"    / /", r"    \ \", r".___/ /", r"\____/ ",
+            ],
+            '4' => &[
+                r"   ___ ", r"  /   |", r" / /| |", r"/ /_| |", r"\___  |", r"    |_/",
+            ],
+            '5' => &[
+                r" _____ ", r"|  ___|", r"|___ \ ", r"    \ \", r"/\__/ /", r"\____/ ",
+            ],
+            '6' => &[
+                r"  ____ ", r" / ___|", r"/ /___ ", r"| ___ \", r"| \_/ |", r"\_____/",
+            ],
+            '7' => &[
+                r" ______", r"|___  /", r"   / / ", r"  / /  ", r"./ /   ", r"\_/    ",
+            ],
+            '8' => &[
+                r" _____ ", r"|  _  |", r" \ V / ", r" / _ \ ", r"| |_| |", r"\_____/",
+            ],
+            '9' => &[
+                r" _____ ", r"|  _  |", r"| |_| |", r"\____ |", r".___/ /", r"\____/ ",
+            ],
+            ':' => &[r"   ", r" _ ", r"(_)", r"   ", r" _ ", r"(_)"],
+            ';' => &[r"   ", r" _ ", r"(_)", r"   ", r" _ ", r"( )", r"|/ "],
+            '<' => &[r"   __", r"  / /", r" / / ", r"< <  ", r" \ \ ", r"  \_\"],
+            '=' => &[
+                r"        ",
+                r" ______ ",
+                r"|______|",
+                r" ______ ",
+                r"|______|",
+            ],
+            '>' => &[r"__   ", r"\ \  ", r" \ \ ", r"  > >", r" / / ", r"/_/  "],
+            '?' => &[
+                r" ___  ", r"|__ \ ", r"   ) |", r"  / / ", r" |_|  ", r" (_)  ",
+            ],
+            '@' => &[
+                r"         ",
+                r"   ____  ",
+                r"  / __ \ ",
+                r" / / _` |",
+                r"| | (_| |",
+                r" \ \__,_|",
+                r"  \____/ ",
+            ],
+            'A' => &[
+                r"  ___  ", r" / _ \ ", r"/ /_\ \", r"|  _  |", r"| | | |", r"\_| |_/",
+            ],
+            'B' => &[
+                r"______ ", r"| ___ \", r"| |_/ /", r"| ___ \", r"| |_/ /", r"\____/ ",
+            ],
+            'C' => &[
+                r" _____ ", r"/  __ \", r"| /  \/", r"| |    ", r"| \__/\", r" \____/",
+            ],
+            'D' => &[
+                r"______ ", r"|  _  \", r"| | | |", r"| | | |", r"| |/ / ", r"|___/  ",
+            ],
+            'E' => &[
+                r" _____ ", r"|  ___|", r"| |__  ", r"|  __| ", r"| |___ ", r"\____/ ",
+            ],
+            'F' => &[
+                r"______ ", r"|  ___|", r"| |_   ", r"|  _|  ", r"| |    ", r"\_|    ",
+            ],
+            'G' => &[
+                r" _____ ", r"|  __ \", r"| |  \/", r"| | __ ", r"| |_\ \", r" \____/",
+            ],
+            'H' => &[
+                r" _   _ ", r"| | | |", r"| |_| |", r"|  _  |", r"| | | |", r"\_| |_/",
+            ],
+            'I' => &[
+                r" _____ ", r"|_   _|", r"  | |  ", r"  | |  ", r" _| |_ ", r" \___/ ",
+            ],
+            'J' => &[
+                r"   ___ ", r"  |_  |", r"    | |", r"    | |", r"/\__/ /", r"\____/ ",
+            ],
+            'K' => &[
+                r" _   __", r"| | / /", r"| |/ / ", r"|    \ ", r"| |\  \", r"\_| \_/",
+            ],
+            'L' => &[
+                r" _     ", r"| |    ", r"| |    ", r"| |    ", r"| |____", r"\_____/",
+            ],
+            'M' => &[
+                r"___  ___",
+                r"|  \/  |",
+                r"| .  . |",
+                r"| |\/| |",
+                r"| |  | |",
+                r"\_|  |_/",
+            ],
+            'N' => &[
+                r" _   _ ", r"| \ | |", r"|  \| |", r"| . ` |", r"| |\  |", r"\_| \_/",
+            ],
+            'O' => &[
+                r" _____ ", r"|  _  |", r"| | | |", r"| | | |", r"\ \_/ /", r" \___/ ",
+            ],
+            'P' => &[
+                r"______ ", r"| ___ \", r"| |_/ /", r"|  __/ ", r"| |    ", r"\_|    ",
+            ],
+            'Q' => &[
+                r" _____ ", r"|  _  |", r"| | | |", r"| | | |", r"\ \/' /", r" \_/\_\",
+            ],
+            'R' => &[
+                r"______ ", r"| ___ \", r"| |_/ /", r"|    / ", r"| |\ \ ", r"\_| \_|",
+            ],
+            'S' => &[
+                r" _____ ", r"/  ___|", r"\ `--. ", r" `--. \", r"/\__/ /", r"\____/ ",
+            ],
+            'T' => &[
+                r" _____ ", r"|_   _|", r"  | |  ", r"  | |  ", r"  | |  ", r"  \_/  ",
+            ],
+            'U' => &[
+                r" _   _ ", r"| | | |", r"| | | |", r"| | | |", r"| |_| |", r" \___/ ",
+            ],
+            'V' => &[
+                r" _   _ ", r"| | | |", r"| | | |", r"| | | |", r"\ \_/ /", r" \___/ ",
+            ],
+            'W' => &[
+                r" _    _ ",
+                r"| |  | |",
+                r"| |  | |",
+                r"| |/\| |",
+                r"\  /\  /",
+                r" \/  \/ ",
+            ],
+            'X' => &[
+                r"__   __", r"\ \ / /", r" \ V / ", r" /   \ ", r"/ /^\ \", r"\/   \/",
+            ],
+            'Y' => &[
+                r"__   __", r"\ \ / /", r" \ V / ", r"  \ /  ", r"  | |  ", r"  \_/  ",
+            ],
+            'Z' => &[
+                r" ______", r"|___  /", r"   / / ", r"  / /  ", r"./ /___", r"\_____/",
+            ],
+            '[' => &[
+                r" ___ ", r"|  _|", r"| |  ", r"| |  ", r"| |  ", r"| |_ ", r"|___|",
+            ],
+            '\\' => &[
+                r"__    ", r"\ \   ", r" \ \  ", r"  \ \ ", r"   \ \", r"    \_\",
+            ],
+            ']' => &[
+                r" ___ ", r"|_  |", r"  | |", r"  | |", r"  | |", r" _| |", r"|___|",
+            ],
+            '^' => &[r" /\ ", r"|/\|"],
+            '_' => &[
+                r"        ",
+                r"        ",
+                r"        ",
+                r"        ",
+                r"        ",
+                r" ______ ",
+                r"|______|",
+            ],
+            '`' => &[r" _ ", r"( )", r" \|"],
+            'a' => &[
+                r"       ", r"       ", r"  __ _ ", r" / _` |", r"| (_| |", r" \__,_|",
+            ],
+            'b' => &[
+                r" _     ", r"| |    ", r"| |__  ", r"| '_ \ ", r"| |_) |", r"|_.__/ ",
+            ],
+            'c' => &[
+                r"      ", r"      ", r"  ___ ", r" / __|", r"| (__ ", r" \___|",
+            ],
+            'd' => &[
+                r"     _ ", r"    | |", r"  __| |", r" / _` |", r"| (_| |", r" \__,_|",
+            ],
+            'e' => &[
+                r"      ", r"      ", r"  ___ ", r" / _ \", r"|  __/", r" \___|",
+            ],
+            'f' => &[r"  __ ", r" / _|", r"| |_ ", r"|  _|", r"| |  ", r"|_|  "],
+            'g' => &[
+                r"       ", r"       ", r"  __ _ ", r" / _` |", r"| (_| |", r" \__, |", r"  __/ |",
+                r" |___/ ",
+            ],
+            'h' => &[
+                r" _     ", r"| |    ", r"| |__  ", r"| '_ \ ", r"| | | |", r"|_| |_|",
+            ],
+            'i' => &[r" _ ", r"(_)", r" _ ", r"| |", r"| |", r"|_|"],
+            'j' => &[
+                r"   _ ", r"  (_)", r"   _ ", r"  | |", r"  | |", r"  | |", r" _/ |", r"|__/ ",
+            ],
+            'k' => &[
+                r" _    ", r"| |   ", r"| | __", r"| |/ /", r"|   < ", r"|_|\_\",
+            ],
+            'l' => &[r" _ ", r"| |", r"| |", r"| |", r"| |", r"|_|"],
+            'm' => &[
+                r"           ",
+                r"           ",
+                r" _ __ ___  ",
+                r"| '_ ` _ \ ",
+                r"| | | | | |",
+                r"|_| |_| |_|",
+            ],
+            'n' => &[
+                r"       ", r"       ", r" _ __  ", r"| '_ \ ", r"| | | |", r"|_| |_|",
+            ],
+            'o' => &[
+                r"       ", r"       ", r"  ___  ", r" / _ \ ", r"| (_) |", r" \___/ ",
+            ],
+            'p' => &[
+                r"       ", r"       ", r" _ __  ", r"| '_ \ ", r"| |_) |", r"| .__/ ", r"| |    ",
+                r"|_|    ",
+            ],
+            'q' => &[
+                r"       ", r"       ", r"  __ _ ", r" / _` |", r"| (_| |", r" \__, |", r"    | |",
+                r"    |_|",
+            ],
+            'r' => &[
+                r"      ", r"      ", r" _ __ ", r"| '__|", r"| |   ", r"|_|   ",
+            ],
+            's' => &[r"     ", r"     ", r" ___ ", r"/ __|", r"\__ \", r"|___/"],
+            't' => &[r" _   ", r"| |  ", r"| |_ ", r"| __|", r"| |_ ", r" \__|"],
+            'u' => &[
+                r"       ", r"       ", r" _   _ ", r"| | | |", r"| |_| |", r" \__,_|",
+            ],
+            'v' => &[
+                r"       ", r"       ", r"__   __", r"\ \ / /", r" \ V / ", r"  \_/  ",
+            ],
+            'w' => &[
+                r"          ",
+                r"          ",
+                r"__      __",
+                r"\ \ /\ / /",
+                r" \ V  V / ",
+                r"  \_/\_/  ",
+            ],
+            'x' => &[
+                r"      ", r"      ", r"__  __", r"\ \/ /", r" >  < ", r"/_/\_\",
+            ],
+            'y' => &[
+                r"       ", r"       ", r" _   _ ", r"| | | |", r"| |_| |", r" \__, |", r"  __/ |",
+                r" |___/ ",
+            ],
+            'z' => &[r"     ", r"     ", r" ____", r"|_  /", r" / / ", r"/___|"],
+            '{' => &[
+                r"   __", r"  / /", r" | | ", r"/ /  ", r"\ \  ", r" | | ", r"  \_\",
+            ],
+            '|' => &[
+                r" _ ", r"| |", r"| |", r"| |", r"| |", r"| |", r"| |", r"|_|",
+            ],
+            '}' => &[
+                r"__   ", r"\ \  ", r" | | ", r"  \ \", r"  / /", r" | | ", r"/_/  ",
+            ],
+            '~' => &[r" /\/|", r"|/\/ "],
+            _ => return None,
+        };
+        Some(rows.to_vec())
+    }
+}
+
+// Colossal font: "https://onlineasciitools.com/convert-text-to-ascii-art"
+/// A bigger font, eleven rows high.
+pub struct Colossal;
+impl Font for Colossal {
+    fn height(&self) -> usize { 11 }
+    fn glyph(&self, c: char) -> Option<Vec<&str>> {
+        let rows: &[&str] = match c {
+            ' ' => &["  ", "  ", "  ", "  ", "  ", "  ", "  ", "  "],
+            '!' => &["888", "888", "888", "888", "888", "Y8P", " \" ", "888"],
+            '"' => &["d8b d8b", "88P 88P", "8P  8P ", "\"   \"  "],
+            '#' => &[
+                "               ",
+                "   888  888    ",
+                "   888  888    ",
+                "888888888888888",
+                "   888  888    ",
+                "888888888888888",
+                "   888  888    ",
+                "   888  888    ",
+            ],
+            '$' => &[
+                "    888    ",
+                " .d88888b. ",
+                "d88P 888   ",
+                "Y88b.888   ",
+                " \"Y88888b. ",
+                "     888Y88b",
+                "Y88b 888d88P",
+                " \"Y88888P\" ",
+                "    888    ",
+            ],
+            '%' => &[
+                "d88b   d88P",
+                "Y88P  d88P ",
+                "     d88P  ",
+                "    d88P   ",
+                "   d88P    ",
+                "  d88P     ",
+                " d88P d88b ",
+                "d88P  Y88P ",
+            ],
+            '&' => &[
+                " .d88b.     ",
+                "d8P  Y8b    ",
+                "Y8b. d8P    ",
+                " \"Y888P     ",
+                ".d8P\"Y8b.d8P",
+                "888   Y888P ",
+                "Y88b. d888b ",
+                " \"Y8888P\"Y8b",
+            ],
+            '\'' => &["d8b", "88P", "8P ", "\"  "],
+            '(' => &[
+                " .d8", "d88P", "888 ", "888 ", "888 ", "888 ", "Y88b", " \"Y8",
+            ],
+            ')' => &[
+                "8b. ", "Y88b", " 888", " 888", " 888", " 888", "d88P", "8P\" ",
+            ],
+            '*' => &[
+                "         ",
+                "         ",
+                "o   8   o",
+                " Y8b8d8P ",
+                "d8888888b",
+                " d8P8Y8b ",
+                "o   8   o",
+            ],
+            '+' => &[
+                "          ",
+                "          ",
+                "    888   ",
+                "    888   ",
+                "8888888888",
+                "    888   ",
+                "    888   ",
+            ],
+            ',' => &[
+                "   ", "   ", "   ", "   ", "   ", "   ", "d8b", "88P", "8P ", "\"  ",
+            ],
+            '-' => &["      ", "      ", "      ", "      ", "888888"],
+            '.' => &["   ", "   ", "   ", "   ", "   ", "   ", "d8b", "Y8P"],
+            '/' => &[
+                "       d88P",
+                "      d88P ",
+                "     d88P  ",
+                "    d88P   ",
+                "   d88P    ",
+                "  d88P     ",
+                " d88P      ",
+                "d88P       ",
+            ],
+            '0' => &[
+                " .d8888b. ",
+                "d88P  Y88b",
+                "888    888",
+                "888    888",
+                "888    888",
+                "888    888",
+                "Y88b  d88P",
+                " \"Y8888P\" ",
+            ],
+            '1' => &[
+                " d888  ", "d8888  ", "  888  ", "  888  ", "  888  ", "  888  ", "  888  ",
+                "8888888",
+            ],
+            '2' => &[
+                " .d8888b.  ",
+                "d88P  Y88b ",
+                "       888 ",
+                "     .d88P ",
+                " .od888P\"  ",
+                "d88P\"      ",
+                "888\"       ",
+                "888888888  ",
+            ],
+            '3' => &[
+                " .d8888b. ",
+                "d88P  Y88b",
+                "     .d88P",
+                "    8888\" ",
+                "     \"Y8b.",
+                "888    888",
+                "Y88b  d88P",
+                " \"Y8888P\" ",
+            ],
+            '4' => &[
+                "    d8888 ",
+                "   d8P888 ",
+                "  d8P 888 ",
+                " d8P  888 ",
+                "d88   888 ",
+                "8888888888",
+                "      888 ",
+                "      888 ",
+            ],
+            '5' => &[
+                "888888888 ",
+                "888       ",
+                "888       ",
+                "8888888b. ",
+                "     \"Y88b",
+                "       888",
+                "Y88b  d88P",
+                " \"Y8888P\" ",
+            ],
+            '6' => &[
+                " .d8888b. ",
+                "d88P  Y88b",
+                "888       ",
+                "888d888b. ",
+                "888P \"Y88b",
+                "888    888",
+                "Y88b  d88P",
+                " \"Y8888P\" ",
+            ],
+            '7' => &[
+                "8888888888",
+                "      d88P",
+                "     d88P ",
+                "    d88P  ",
+                " 88888888 ",
+                "  d88P    ",
+                " d88P     ",
+                "d88P      ",
+            ],
+            '8' => &[
+                " .d8888b.  ",
+                "d88P  Y88b ",
+                "Y88b. d88P ",
+                " \"Y88888\"  ",
+                ".d8P\"\"Y8b. ",
+                "888    888 ",
+                "Y88b  d88P ",
+                " \"Y8888P\"  ",
+            ],
+            '9' => &[
+                " .d8888b. ",
+                "d88P  Y88b",
+                "888    888",
+                "Y88b. d888",
+                " \"Y888P888",
+                "       888",
+                "Y88b  d88P",
+                " \"Y8888P\" ",
+            ],
+            ':' => &["   ", "d8b", "Y8P", "   ", "   ", "d8b", "Y8P", "   "],
+            ';' => &[
+                "   ", "d8b", "Y8P", "   ", "   ", "d8b", "88P", "8P ", "\"  ",
+            ],
+            '<' => &[
+                "          ",
+                "      d88P",
+                "   .d88P\" ",
+                ".d88P\"    ",
+                "d88P\"     ",
+                " \"Y88b.   ",
+                "    \"Y88b.",
+                "       Y88",
+            ],
+            '=' => &[
+                "          ",
+                "          ",
+                "8888888888",
+                "          ",
+                "8888888888",
+            ],
+            '>' => &[
+                "          ",
+                "Y88b      ",
+                " \"Y88b.   ",
+                "    \"Y88b.",
+                "     \"Y88b",
+                "    .d88P\"",
+                " .d88P\"   ",
+                "d88P      ",
+            ],
+            '?' => &[
+                " .d8888b. ",
+                "d88P  Y88b",
+                "     .d88P",
+                "   .d88P\" ",
+                "   888\"   ",
+                "   888    ",
+                "          ",
+                "   888    ",
+            ],
+            '@' => &[
+                "  .d8888888b.  ",
+                " d88P\"   \"Y88b ",
+                "888  d8b d8b 888",
+                "888  888 888 888",
+                "888  888 888 888",
+                "888  Y88888P 88P",
+                "Y88b.      .d88P",
+                " \"Y8888888888P\" ",
+            ],
+            'A' => &[
+                "       d8888",
+                "      d88888",
+                "     d88P888",
+                "    d88P 888",
+                "   d88P  888",
+                "  d88P   888",
+                " d8888888888",
+                "d88P     888",
+            ],
+            'B' => &[
+                "888888b.  ",
+                "888  \"88b ",
+                "888  .88P ",
+                "8888888K. ",
+                "888  \"Y88b",
+                "888    888",
+                "888   d88P",
+                "8888888P\" ",
+            ],
+            'C' => &[
+                " .d8888b. ",
+                "d88P  Y88b",
+                "888    888",
+                "888       ",
+                "888       ",
+                "888    888",
+                "Y88b  d88P",
+                " \"Y8888P\" ",
+            ],
+            'D' => &[
+                "8888888b. ",
+                "888  \"Y88b",
+                "888    888",
+                "888    888",
+                "888    888",
+                "888    888",
+                "888  .d88P",
+                "8888888P\" ",
+            ],
+            'E' => &[
+                "8888888888",
+                "888       ",
+                "888       ",
+                "8888888   ",
+                "888       ",
+                "888       ",
+                "888       ",
+                "8888888888",
+            ],
+            'F' => &[
+                "8888888888",
+                "888       ",
+                "888       ",
+                "8888888   ",
+                "888       ",
+                "888       ",
+                "888       ",
+                "888       ",
+            ],
+            'G' => &[
+                " .d8888b. ",
+                "d88P  Y88b",
+                "888    888",
+                "888       ",
+                "888  88888",
+                "888    888",
+                "Y88b  d88P",
+                " \"Y8888P88",
+            ],
+            'H' => &[
+                "888    888",
+                "888    888",
+                "888    888",
+                "8888888888",
+                "888    888",
+                "888    888",
+                "888    888",
+                "888    888",
+            ],
+            'I' => &[
+                "8888888", "  888  ", "  888  ", "  888  ", "  888  ", "  888  ", "  888  ",
+                "8888888",
+            ],
+            'J' => &[
+                "  888888",
+                "    \"88b",
+                "     888",
+                "     888",
+                "     888",
+                "     888",
+                "     88P",
+                "     888",
+                "   .d88P",
+                " .d88P\" ",
+                "888P\"   ",
+            ],
+            'K' => &[
+                "888    d8P ",
+                "888   d8P  ",
+                "888  d8P   ",
+                "888d88K    ",
+                "8888888b   ",
+                "888  Y88b  ",
+                "888   Y88b ",
+                "888    Y88b",
+            ],
+            'L' => &[
+                "888     ", "888     ", "888     ", "888     ", "888     ", "888     ", "888     ",
+                "88888888",
+            ],
+            'M' => &[
+                "888b     d888",
+                "8888b   d8888",
+                "88888b.d88888",
+                "888Y88888P888",
+                "888 Y888P 888",
+                "888  Y8P  888",
+                "888   \"   888",
+                "888       888",
+            ],
+            'N' => &[
+                "888b    888",
+                "8888b   888",
+                "88888b  888",
+                "888Y88b 888",
+                "888 Y88b888",
+                "888  Y88888",
+                "888   Y8888",
+                "888    Y888",
+            ],
+            'O' => &[
+                " .d88888b. ",
+                "d88P\" \"Y88b",
+                "888     888",
+                "888     888",
+                "888     888",
+                "888     888",
+                "Y88b. .d88P",
+                " \"Y88888P\" ",
+            ],
+            'P' => &[
+                "8888888b. ",
+                "888   Y88b",
+                "888    888",
+                "888   d88P",
+                "8888888P\" ",
+                "888       ",
+                "888       ",
+                "888       ",
+            ],
+            'Q' => &[
+                " .d88888b. ",
+                "d88P\" \"Y88b",
+                "888     888",
+                "888     888",
+                "888     888",
+                "888 Y8b 888",
+                "Y88b.Y8b88P",
+                " \"Y888888\" ",
+                "       Y8b ",
+            ],
+            'R' => &[
+                "8888888b. ",
+                "888   Y88b",
+                "888    888",
+                "888   d88P",
+                "8888888P\" ",
+                "888 T88b  ",
+                "888  T88b ",
+                "888   T88b",
+            ],
+            'S' => &[
+                " .d8888b. ",
+                "d88P  Y88b",
+                "Y88b.     ",
+                " \"Y888b.  ",
+                "    \"Y88b.",
+                "      \"888",
+                "Y88b  d88P",
+                " \"Y8888P\" ",
+            ],
+            'T' => &[
+                "88888888888",
+                "    888    ",
+                "    888    ",
+                "    888    ",
+                "    888    ",
+                "    888    ",
+                "    888    ",
+                "    888    ",
+            ],
+            'U' => &[
+                "888     888",
+                "888     888",
+                "888     888",
+                "888     888",
+                "888     888",
+                "888     888",
+                "Y88b. .d88P",
+                " \"Y88888P\" ",
+            ],
+            'V' => &[
+                "888     888",
+                "888     888",
+                "888     888",
+                "Y88b   d88P",
+                " Y88b d88P ",
+                "  Y88o88P  ",
+                "   Y888P   ",
+                "    Y8P    ",
+            ],
+            'W' => &[
+                "888       888",
+                "888   o   888",
+                "888  d8b  888",
+                "888 d888b 888",
+                "888d88888b888",
+                "88888P Y88888",
+                "8888P   Y8888",
+                "888P     Y888",
+            ],
+            'X' => &[
+                "Y88b   d88P",
+                " Y88b d88P ",
+                "  Y88o88P  ",
+                "   Y888P   ",
+                "   d888b   ",
+                "  d88888b  ",
+                " d88P Y88b ",
+                "d88P   Y88b",
+            ],
+            'Y' => &[
+                "Y88b   d88P",
+                " Y88b d88P ",
+                "  Y88o88P  ",
+                "   Y888P   ",
+                "    888    ",
+                "    888    ",
+                "    888    ",
+                "    888    ",
+            ],
+            'Z' => &[
+                "8888888888P",
+                "      d88P ",
+                "     d88P  ",
+                "    d88P   ",
+                "   d88P    ",
+                "  d88P     ",
+                " d88P      ",
+                "d8888888888",
+            ],
+            '[' => &[
+                "8888", "888 ", "888 ", "888 ", "888 ", "888 ", "888 ", "8888",
+            ],
+            '\\' => &[
+                "Y88b       ",
+                " Y88b      ",
+                "  Y88b     ",
+                "   Y88b    ",
+                "    Y88b   ",
+                "     Y88b  ",
+                "      Y88b ",
+                "       Y88b",
+            ],
+            ']' => &[
+                "8888", " 888", " 888", " 888", " 888", " 888", " 888", "8888",
+            ],
+            '^' => &["  d88b  ", " d8P\"\"8b ", "d8P  Y8b"],
+            '_' => &[
+                "          ",
+                "          ",
+                "          ",
+                "          ",
+                "          ",
+                "          ",
+                "          ",
+                "          ",
+                "8888888888",
+            ],
+            '`' => &["d8b ", "Y88b", " Y88"],
+            'a' => &[
+                "        ",
+                "        ",
+                "        ",
+                " 8888b. ",
+                "    \"88b",
+                ".d888888",
+                "888  888",
+                "\"Y888888",
+            ],
+            'b' => &[
+                "888     ",
+                "888     ",
+                "888     ",
+                "88888b. ",
+                "888 \"88b",
+                "888  888",
+                "888 d88P",
+                "88888P\" ",
+            ],
+            'c' => &[
+                "        ",
+                "        ",
+                "        ",
+                " .d8888b",
+                "d88P\"   ",
+                "888     ",
+                "Y88b.   ",
+                " \"Y8888P",
+            ],
+            'd' => &[
+                "     888",
+                "     888",
+                "     888",
+                " .d88888",
+                "d88\" 888",
+                "888  888",
+                "Y88b 888",
+                " \"Y88888",
+            ],
+            'e' => &[
+                "        ",
+                "        ",
+                "        ",
+                " .d88b. ",
+                "d8P  Y8b",
+                "88888888",
+                "Y8b.    ",
+                " \"Y8888 ",
+            ],
+            'f' => &[
+                " .d888", "d88P\" ", "888   ", "888888", "888   ", "888   ", "888   ", "888   ",
+            ],
+            'g' => &[
+                "        ",
+                "        ",
+                "        ",
+                " .d88b. ",
+                "d88P\"88b",
+                "888  888",
+                "Y88b 888",
+                " \"Y88888",
+                "     888",
+                "Y8b d88P",
+                " \"Y88P\" ",
+            ],
+            'h' => &[
+                "888     ",
+                "888     ",
+                "888     ",
+                "88888b. ",
+                "888 \"88b",
+                "888  888",
+                "888  888",
+                "888  888",
+            ],
+            'i' => &["d8b", "Y8P", "   ", "888", "888", "888", "888", "888"],
+            'j' => &[
+                "   d8b", "   Y8P", "      ", "  8888", "  \"888", "   888", "   888", "   888",
+                "   888", "  d88P", "888P\" ",
+            ],
+            'k' => &[
+                "888     ",
+                "888     ",
+                "888     ",
+                "888  888",
+                "888 .88P",
+                "888888K ",
+                "888 \"88b",
+                "888  888",
+            ],
+            'l' => &["888", "888", "888", "888", "888", "888", "888", "888"],
+            'm' => &[
+                "             ",
+                "             ",
+                "             ",
+                "88888b.d88b. ",
+                "888 \"888 \"88b",
+                "888  888  888",
+                "888  888  888",
+                "888  888  888",
+            ],
+            'n' => &[
+                "        ",
+                "        ",
+                "        ",
+                "88888b. ",
+                "888 \"88b",
+                "888  888",
+                "888  888",
+                "888  888",
+            ],
+            'o' => &[
+                "        ",
+                "        ",
+                "        ",
+                " .d88b. ",
+                "d88\"\"88b",
+                "888  888",
+                "Y88..88P",
+                " \"Y88P\" ",
+            ],
+            'p' => &[
+                "        ",
+                "        ",
+                "        ",
+                "88888b. ",
+                "888 \"88b",
+                "888  888",
+                "888 d88P",
+                "88888P\" ",
+                "888     ",
+                "888     ",
+                "888     ",
+            ],
+            'q' => &[
+                "        ",
+                "        ",
+                "        ",
+                " .d88888",
+                "d88\" 888",
+                "888  888",
+                "Y88b 888",
+                " \"Y88888",
+                "     888",
+                "     888",
+                "     888",
+            ],
+            'r' => &[
+                "       ", "       ", "       ", "888d888", "888P\"  ", "888    ", "888    ",
+                "888    ",
+            ],
+            's' => &[
+                "        ",
+                "        ",
+                "        ",
+                ".d8888b ",
+                "88K     ",
+                "\"Y8888b.",
+                "     X88",
+                " 88888P'",
+            ],
+            't' => &[
+                "888    ", "888    ", "888    ", "888888 ", "888    ", "888    ", "Y88b.  ",
+                " \"Y888 ",
+            ],
+            'u' => &[
+                "        ",
+                "        ",
+                "        ",
+                "888  888",
+                "888  888",
+                "888  888",
+                "Y88b 888",
+                " \"Y88888",
+            ],
+            'v' => &[
+                "        ", "        ", "        ", "888  888", "888  888", "Y88  88P", " Y8bd8P ",
+                "  Y88P  ",
+            ],
+            'w' => &[
+                "             ",
+                "             ",
+                "             ",
+                "888  888  888",
+                "888  888  888",
+                "888  888  888",
+                "Y88b 888 d88P",
+                " \"Y8888888P\" ",
+            ],
+            'x' => &[
+                "        ",
+                "        ",
+                "        ",
+                "888  888",
+                "`Y8bd8P'",
+                "  X88K  ",
+                ".d8\"\"8b.",
+                "888  888",
+            ],
+            'y' => &[
+                "        ",
+                "        ",
+                "        ",
+                "888  888",
+                "888  888",
+                "888  888",
+                "Y88b 888",
+                " \"Y88888",
+                "     888",
+                "Y8b d88P",
+                " \"Y88P\" ",
+            ],
+            'z' => &[
+                "        ", "        ", "        ", "88888888", "   d88P ", "  d88P  ", " d88P   ",
+                "88888888",
+            ],
+            '{' => &[
+                "  .d88", " d88\" ", " 888  ", ".88P  ", "\"88b  ", " 888  ", " Y88b.", "  \"Y88",
+            ],
+            '|' => &[
+                "888", "888", "888", "888", "888", "888", "888", "888", "888", "888", "888",
+            ],
+            '}' => &[
+                "88b.  ", " \"88b ", "  888 ", "  Y88.", "  d88\"", "  888 ", " .d88P", "88P\"  ",
+            ],
+            '~' => &[" .d88b.  .d8", "d8P\"\"Y8bd8P ", "8P    \"Y88P\""],
+            _ => return None,
+        };
+        Some(rows.to_vec())
+    }
+}
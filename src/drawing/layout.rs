@@ -0,0 +1,185 @@
+use super::{Anchor, Draw, Rect, Size};
+use std::io::Write;
+
+/// Children drawn top to bottom, left edges lined up.
+pub struct VStack {
+    children: Vec<Box<dyn Draw>>,
+    gap: u16,
+}
+
+impl VStack {
+    /// `children` with no gap between them.
+    pub fn from(children: Vec<Box<dyn Draw>>) -> Self { Self { children, gap: 0 } }
+
+    /// Blank rows between children.
+    pub fn with_gap(self, gap: u16) -> Self { Self { gap, ..self } }
+}
+
+impl Draw for VStack {
+    fn size(&self) -> Size {
+        let rows = self
+            .children
+            .iter()
+            .map(|c| c.size().height + 1)
+            .sum::<u16>()
+            + self.gap * (self.children.len() as u16).saturating_sub(1);
+        Size::from(
+            rows.saturating_sub(1),
+            self.children
+                .iter()
+                .map(|c| c.size().width)
+                .max()
+                .unwrap_or(0),
+        )
+    }
+    fn draw(&self, writer: &mut dyn Write, x: u16, y: u16) -> std::io::Result<Vec<Rect>> {
+        let mut taken = Vec::new();
+        let mut y0 = y;
+        for child in &self.children {
+            taken.extend(child.draw(writer, x, y0)?);
+            y0 += child.size().height + 1 + self.gap;
+        }
+        Ok(taken)
+    }
+    fn glyphs(&self, x: u16, y: u16) -> Vec<Rect> {
+        let mut glyphs = Vec::new();
+        let mut y0 = y;
+        for child in &self.children {
+            glyphs.extend(child.glyphs(x, y0));
+            y0 += child.size().height + 1 + self.gap;
+        }
+        glyphs
+    }
+}
+
+/// Children drawn left to right, top edges lined up.
+pub struct HStack {
+    children: Vec<Box<dyn Draw>>,
+    gap: u16,
+}
+
+impl HStack {
+    /// `children` with no gap between them.
+    pub fn from(children: Vec<Box<dyn Draw>>) -> Self { Self { children, gap: 0 } }
+
+    /// Blank columns between children.
+    pub fn with_gap(self, gap: u16) -> Self { Self { gap, ..self } }
+}
+
+impl Draw for HStack {
+    fn size(&self) -> Size {
+        Size::from(
+            self.children
+                .iter()
+                .map(|c| c.size().height)
+                .max()
+                .unwrap_or(0),
+            self.children.iter().map(|c| c.size().width).sum::<u16>()
+                + self.gap * (self.children.len() as u16).saturating_sub(1),
+        )
+    }
+    fn draw(&self, writer: &mut dyn Write, x: u16, y: u16) -> std::io::Result<Vec<Rect>> {
+        let mut taken = Vec::new();
+        let mut x0 = x;
+        for child in &self.children {
+            taken.extend(child.draw(writer, x0, y)?);
+            x0 += child.size().width + self.gap;
+        }
+        Ok(taken)
+    }
+    fn glyphs(&self, x: u16, y: u16) -> Vec<Rect> {
+        let mut glyphs = Vec::new();
+        let mut x0 = x;
+        for child in &self.children {
+            glyphs.extend(child.glyphs(x0, y));
+            x0 += child.size().width + self.gap;
+        }
+        glyphs
+    }
+}
+
+/// Empty space around a widget. The rain still falls through it.
+pub struct Padding {
+    inner: Box<dyn Draw>,
+    x: u16,
+    y: u16,
+}
+
+impl Padding {
+    /// `x` columns on each side and `y` rows above and below `inner`.
+    pub fn from(inner: Box<dyn Draw>, x: u16, y: u16) -> Self { Self { inner, x, y } }
+}
+
+impl Draw for Padding {
+    fn size(&self) -> Size {
+        let inner = self.inner.size();
+        Size::from(inner.height + 2 * self.y, inner.width + 2 * self.x)
+    }
+    fn draw(&self, writer: &mut dyn Write, x: u16, y: u16) -> std::io::Result<Vec<Rect>> {
+        self.inner.draw(writer, x + self.x, y + self.y)
+    }
+    fn glyphs(&self, x: u16, y: u16) -> Vec<Rect> { self.inner.glyphs(x + self.x, y + self.y) }
+}
+
+/// A widget that claims `size` no matter what it needs, so a changing widget
+/// doesn't push its neighbours around. Nothing is cut off, so `size` has to
+/// be big enough for the widget at its biggest or it spills over into space
+/// that's been handed out to others.
+pub struct FixedSize {
+    inner: Box<dyn Draw>,
+    size: Size,
+}
+
+impl FixedSize {
+    /// `inner`, taking up exactly `size`.
+    pub fn from(inner: Box<dyn Draw>, size: Size) -> Self { Self { inner, size } }
+}
+
+impl Draw for FixedSize {
+    fn size(&self) -> Size { self.size }
+    fn draw(&self, writer: &mut dyn Write, x: u16, y: u16) -> std::io::Result<Vec<Rect>> {
+        self.inner.draw(writer, x, y)
+    }
+    fn glyphs(&self, x: u16, y: u16) -> Vec<Rect> { self.inner.glyphs(x, y) }
+}
+
+/// A widget placed inside a box of `size` by an anchor, e.g. centered in a
+/// column of wider widgets. Drawn at the top left if it doesn't fit.
+pub struct Aligned {
+    inner: Box<dyn Draw>,
+    anchor: Anchor,
+    size: Size,
+}
+
+impl Aligned {
+    /// `inner` where `anchor` puts it in a box of `size`.
+    pub fn from(inner: Box<dyn Draw>, anchor: Anchor, size: Size) -> Self {
+        Self {
+            inner,
+            anchor,
+            size,
+        }
+    }
+
+    // where the inner widget goes when this is drawn at (`x`, `y`)
+    fn inner_at(&self, x: u16, y: u16) -> (u16, u16) {
+        let screen = (self.size.width, self.size.height + 1);
+        let (x0, y0) = self
+            .anchor
+            .place(self.inner.size(), (0, 0), screen)
+            .unwrap_or((1, 1));
+        (x + x0 - 1, y + y0 - 1)
+    }
+}
+
+impl Draw for Aligned {
+    fn size(&self) -> Size { self.size }
+    fn draw(&self, writer: &mut dyn Write, x: u16, y: u16) -> std::io::Result<Vec<Rect>> {
+        let (x0, y0) = self.inner_at(x, y);
+        self.inner.draw(writer, x0, y0)
+    }
+    fn glyphs(&self, x: u16, y: u16) -> Vec<Rect> {
+        let (x0, y0) = self.inner_at(x, y);
+        self.inner.glyphs(x0, y0)
+    }
+}
@@ -0,0 +1,211 @@
+use super::Size;
+use unicode_width::UnicodeWidthChar;
+
+/// Cells from (`x`, `y`) to `width` columns right and `height` rows down.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Rect {
+    /// Column of the left edge, counting from 1.
+    pub x: u16,
+    /// Row of the top edge, counting from 1.
+    pub y: u16,
+    /// Columns across.
+    pub width: u16,
+    /// Rows down.
+    pub height: u16,
+}
+
+impl Rect {
+    /// `width` by `height` cells from (`x`, `y`).
+    pub fn new(x: u16, y: u16, width: u16, height: u16) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// The cells a widget of `size` drawn at (`x`, `y`) covers.
+    pub fn covering(x: u16, y: u16, size: Size) -> Self {
+        Self::new(x, y, size.width, size.height + 1)
+    }
+
+    /// Overlapping or touching, with no gap between them.
+    pub fn touches(&self, other: &Rect) -> bool {
+        self.x <= other.x + other.width
+            && other.x <= self.x + self.width
+            && self.y <= other.y + other.height
+            && other.y <= self.y + self.height
+    }
+
+    /// Whether (`x`, `y`) is one of the cells.
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        (self.x..self.x + self.width).contains(&x) && (self.y..self.y + self.height).contains(&y)
+    }
+
+    /// Every cell, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = (u16, u16)> {
+        let Rect {
+            x,
+            y,
+            width,
+            height,
+        } = *self;
+        (y..y + height).flat_map(move |y0| (x..x + width).map(move |x0| (x0, y0)))
+    }
+}
+
+/// Cells from (`x`, `y`) rightwards holding something other than spaces in
+/// `line`, one rect per run.
+pub(super) fn runs(x: u16, y: u16, line: &str) -> Vec<Rect> {
+    let mut rects: Vec<Rect> = Vec::new();
+    let mut column = x;
+    let mut joined = false;
+    for c in line.chars() {
+        let width = c.width().unwrap_or(0) as u16;
+        if c == ' ' {
+            joined = false;
+        } else if joined {
+            if let Some(last) = rects.last_mut() {
+                last.width += width;
+            }
+        } else {
+            rects.push(Rect::new(column, y, width, 1));
+            joined = true;
+        }
+        column += width;
+    }
+    rects
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Cover {
+    Clear,
+    // inside a widget but empty, the rain shows through dimmed
+    Dim,
+    Solid,
+}
+
+/// Which cells of the screen widgets cover, so the rain can stay out of them
+/// with one lookup per cell.
+pub struct Mask {
+    width: u16,
+    height: u16,
+    cells: Vec<Cover>,
+}
+
+impl Mask {
+    /// Nothing covered yet on a `screen` sized terminal.
+    pub fn new(screen: (u16, u16)) -> Self {
+        Self {
+            width: screen.0,
+            height: screen.1,
+            cells: vec![Cover::Clear; screen.0 as usize * screen.1 as usize],
+        }
+    }
+
+    /// Keeps the rain out of `rect`.
+    pub fn cover(&mut self, rect: &Rect) {
+        for (x, y) in rect.cells() {
+            if let Some(i) = self.index(x, y) {
+                self.cells[i] = Cover::Solid;
+            }
+        }
+    }
+
+    /// Lets the rain through `rect`, dimmed, where nothing covers it.
+    pub fn shade(&mut self, rect: &Rect) {
+        for (x, y) in rect.cells() {
+            if let Some(i) = self.index(x, y) {
+                if self.cells[i] == Cover::Clear {
+                    self.cells[i] = Cover::Dim;
+                }
+            }
+        }
+    }
+
+    /// Whether the rain has to stay out of (`x`, `y`).
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        matches!(self.index(x, y), Some(i) if self.cells[i] == Cover::Solid)
+    }
+
+    /// Whether the rain at (`x`, `y`) should be drawn dimmed.
+    pub fn dims(&self, x: u16, y: u16) -> bool {
+        matches!(self.index(x, y), Some(i) if self.cells[i] == Cover::Dim)
+    }
+
+    /// Whether the rain looks the same at (`x`, `y`) under both masks.
+    pub fn same_at(&self, other: &Mask, x: u16, y: u16) -> bool {
+        self.contains(x, y) == other.contains(x, y) && self.dims(x, y) == other.dims(x, y)
+    }
+
+    // screen coordinates start at 1, anything off screen isn't covered
+    fn index(&self, x: u16, y: u16) -> Option<usize> {
+        if x == 0 || y == 0 || x > self.width || y > self.height {
+            return None;
+        }
+        Some((y - 1) as usize * self.width as usize + (x - 1) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_from_one() {
+        let mut mask = Mask::new((10, 5));
+        mask.cover(&Rect::new(1, 1, 1, 1));
+        assert!(mask.contains(1, 1));
+        assert!(!mask.contains(2, 1) && !mask.contains(1, 2));
+        mask.cover(&Rect::new(10, 5, 1, 1));
+        assert!(mask.contains(10, 5));
+        assert!(!mask.contains(9, 5) && !mask.contains(10, 4));
+    }
+
+    #[test]
+    fn off_screen_is_never_covered() {
+        let mut mask = Mask::new((10, 5));
+        mask.cover(&Rect::new(0, 0, 20, 20));
+        mask.shade(&Rect::new(0, 0, 20, 20));
+        for &(x, y) in &[
+            (0, 0),
+            (0, 3),
+            (3, 0),
+            (11, 3),
+            (3, 6),
+            (u16::MAX, u16::MAX),
+        ] {
+            assert!(!mask.contains(x, y) && !mask.dims(x, y));
+        }
+        assert!(mask.contains(1, 1) && mask.contains(10, 5));
+    }
+
+    #[test]
+    fn cover_wins_over_shade() {
+        let mut mask = Mask::new((10, 5));
+        mask.shade(&Rect::new(1, 1, 4, 1));
+        mask.cover(&Rect::new(2, 1, 1, 1));
+        mask.cover(&Rect::new(6, 1, 1, 1));
+        mask.shade(&Rect::new(5, 1, 2, 1));
+        assert!(mask.dims(1, 1) && !mask.contains(1, 1));
+        assert!(mask.contains(2, 1) && !mask.dims(2, 1));
+        assert!(mask.dims(5, 1));
+        assert!(mask.contains(6, 1) && !mask.dims(6, 1));
+        assert!(!mask.contains(7, 1) && !mask.dims(7, 1));
+    }
+
+    #[test]
+    fn runs_of_glyphs() {
+        assert_eq!(
+            runs(3, 2, "ab  c "),
+            vec![Rect::new(3, 2, 2, 1), Rect::new(7, 2, 1, 1)]
+        );
+        assert_eq!(runs(1, 1, "   "), vec![]);
+        // wide characters take up two cells
+        assert_eq!(
+            runs(1, 1, "日本 x"),
+            vec![Rect::new(1, 1, 4, 1), Rect::new(6, 1, 1, 1)]
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use super::{Anchor, Rect, Size};
+
+const ANCHORS: [Anchor; 9] = [
+    Anchor::TopLeft,
+    Anchor::Top,
+    Anchor::TopRight,
+    Anchor::Left,
+    Anchor::Center,
+    Anchor::Right,
+    Anchor::BottomLeft,
+    Anchor::Bottom,
+    Anchor::BottomRight,
+];
+
+/// Hands out screen space one widget at a time. A widget goes where its
+/// anchor puts it if that's free, otherwise to the closest free spot, and
+/// gets nothing if there's no room left. Earlier widgets win.
+pub struct Placer {
+    screen: (u16, u16),
+    taken: Vec<Rect>,
+}
+
+impl Placer {
+    /// Nothing placed yet on a `screen` sized terminal.
+    pub fn new(screen: (u16, u16)) -> Self {
+        Self {
+            screen,
+            taken: Vec::new(),
+        }
+    }
+
+    /// Where to draw something of `size`, see `Anchor::place`.
+    pub fn place(&mut self, size: Size, anchor: Anchor, margin: (u16, u16)) -> Option<(u16, u16)> {
+        // same cells `Rect::covering` covers
+        let (width, height) = (size.width, size.height + 1);
+        let wanted = anchor.place(size, margin, self.screen)?;
+        let mut candidates = vec![wanted];
+        candidates.extend(
+            ANCHORS
+                .iter()
+                .filter_map(|a| a.place(size, margin, self.screen)),
+        );
+        candidates.extend(
+            ANCHORS
+                .iter()
+                .filter_map(|a| a.place(size, (0, 0), self.screen)),
+        );
+        // right next to what's already there, one cell apart
+        for &Rect {
+            x,
+            y,
+            width: w,
+            height: h,
+        } in &self.taken
+        {
+            candidates.push((x, y + h + 1));
+            candidates.push((x, y.saturating_sub(height + 1)));
+            candidates.push((x + w + 1, y));
+            candidates.push((x.saturating_sub(width + 1), y));
+            candidates.push((wanted.0, y + h + 1));
+            candidates.push((wanted.0, y.saturating_sub(height + 1)));
+            candidates.push((x + w + 1, wanted.1));
+            candidates.push((x.saturating_sub(width + 1), wanted.1));
+        }
+        let distance = |&(x, y): &(u16, u16)| {
+            (x as i32 - wanted.0 as i32).abs() + (y as i32 - wanted.1 as i32).abs()
+        };
+        let spot = candidates
+            .into_iter()
+            .filter(|&(x, y)| x >= 1 && y >= 1)
+            .filter(|&(x, y)| x + width - 1 <= self.screen.0 && y + height - 1 <= self.screen.1)
+            .map(|(x, y)| Rect::new(x, y, width, height))
+            .filter(|spot| !self.taken.iter().any(|t| t.touches(spot)))
+            .min_by_key(|spot| distance(&(spot.x, spot.y)))?;
+        self.taken.push(spot);
+        Some((spot.x, spot.y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(at: (u16, u16), size: Size) -> Rect { Rect::covering(at.0, at.1, size) }
+
+    #[test]
+    fn first_widget_goes_where_its_anchor_says() {
+        let mut placer = Placer::new((80, 24));
+        let size = Size::new(3, 10);
+        assert_eq!(placer.place(size, Anchor::Center, (0, 0)), Some((36, 11)));
+        assert_eq!(placer.place(size, Anchor::TopLeft, (2, 1)), Some((3, 2)));
+    }
+
+    #[test]
+    fn widgets_never_touch() {
+        let mut placer = Placer::new((80, 24));
+        let sizes = [
+            Size::new(5, 30),
+            Size::new(3, 20),
+            Size::new(4, 25),
+            Size::new(2, 10),
+        ];
+        let placed: Vec<Rect> = (sizes.iter())
+            .map(|&size| area(placer.place(size, Anchor::Center, (0, 0)).unwrap(), size))
+            .collect();
+        for (i, a) in placed.iter().enumerate() {
+            assert!(a.x >= 1 && a.y >= 1 && a.x + a.width - 1 <= 80 && a.y + a.height - 1 <= 24);
+            for b in &placed[i + 1..] {
+                assert!(!a.touches(b), "{:?} touches {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn falls_back_to_the_closest_free_spot() {
+        let mut placer = Placer::new((80, 24));
+        let size = Size::new(3, 10);
+        let first = placer.place(size, Anchor::Center, (0, 0)).unwrap();
+        // right below the first one, a row apart
+        assert_eq!(
+            placer.place(size, Anchor::Center, (0, 0)),
+            Some((first.0, first.1 + 4))
+        );
+        // the corner it's anchored to is still free
+        assert_eq!(
+            placer.place(size, Anchor::BottomRight, (0, 0)),
+            Some((71, 22))
+        );
+    }
+
+    #[test]
+    fn hides_what_doesnt_fit() {
+        let mut placer = Placer::new((20, 5));
+        assert_eq!(
+            placer.place(Size::new(5, 20), Anchor::Center, (0, 0)),
+            Some((1, 1))
+        );
+        assert_eq!(placer.place(Size::new(1, 1), Anchor::TopLeft, (0, 0)), None);
+        let mut placer = Placer::new((20, 5));
+        assert_eq!(placer.place(Size::new(6, 5), Anchor::Center, (0, 0)), None);
+        assert_eq!(placer.place(Size::new(1, 21), Anchor::Center, (0, 0)), None);
+    }
+}
@@ -0,0 +1,408 @@
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// A run of text drawn with the same SGR attributes.
+pub struct Span {
+    pub style: Sgr,
+    pub text: String,
+}
+
+/// Colors and attributes set by SGR sequences in the text, kept as what they
+/// add up to rather than the sequences themselves.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Sgr {
+    fg: Option<Ansi>,
+    bg: Option<Ansi>,
+    // a bit for each of `ATTRIBUTES`
+    attributes: u8,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Ansi {
+    // the foreground code, 30 to 37 or 90 to 97
+    Basic(u16),
+    Indexed(u16),
+    Rgb(u16, u16, u16),
+}
+
+// the codes turning each attribute on and off, bold and faint share theirs
+const ATTRIBUTES: [(u16, u16); 8] = [
+    (1, 22),
+    (2, 22),
+    (3, 23),
+    (4, 24),
+    (5, 25),
+    (7, 27),
+    (8, 28),
+    (9, 29),
+];
+
+const TAB_WIDTH: usize = 8;
+
+/// Splits text from outside into lines of spans, removing anything that
+/// could move the cursor or change the terminal's state. Tabs become spaces
+/// and backspaces erase the character before them, like on a typewriter.
+/// With `keep_colors` SGR sequences (colors, bold and so on) are kept on the
+/// spans they apply to, every other escape sequence is dropped.
+pub fn parse(text: &str, keep_colors: bool) -> Vec<Vec<Span>> {
+    let mut lines = Vec::new();
+    let mut line: Vec<Span> = Vec::new();
+    let mut style = Sgr::default();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => lines.push(std::mem::take(&mut line)),
+            '\t' => {
+                let column = width(&line);
+                let spaces = TAB_WIDTH - column % TAB_WIDTH;
+                push(&mut line, &style, &" ".repeat(spaces));
+            }
+            '\x08' => {
+                if let Some(span) = line.last_mut() {
+                    span.text.pop();
+                    if span.text.is_empty() {
+                        line.pop();
+                    }
+                }
+            }
+            '\x1b' => match chars.next() {
+                // CSI, parameters then a final byte
+                Some('[') => {
+                    let mut params = String::new();
+                    let mut last = None;
+                    for c in chars.by_ref() {
+                        if ('\x40'..='\x7e').contains(&c) {
+                            last = Some(c);
+                            break;
+                        }
+                        params.push(c);
+                    }
+                    let is_sgr =
+                        last == Some('m') && params.chars().all(|c| c.is_ascii_digit() || c == ';');
+                    if keep_colors && is_sgr {
+                        style.apply(&params);
+                    }
+                }
+                // OSC and friends, terminated by BEL or ST
+                Some(']') | Some('P') | Some('X') | Some('^') | Some('_') => {
+                    while let Some(c) = chars.next() {
+                        if c == '\x07' {
+                            break;
+                        }
+                        if c == '\x1b' && chars.peek() == Some(&'\\') {
+                            chars.next();
+                            break;
+                        }
+                    }
+                }
+                // two character sequences
+                _ => {}
+            },
+            c if c.is_control() => {}
+            c => push(&mut line, &style, &c.to_string()),
+        }
+    }
+    // like `str::lines`, a trailing newline doesn't start another line
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+fn push(line: &mut Vec<Span>, style: &Sgr, text: &str) {
+    match line.last_mut() {
+        Some(span) if span.style == *style => span.text.push_str(text),
+        _ => line.push(Span {
+            style: *style,
+            text: text.to_owned(),
+        }),
+    }
+}
+
+impl Sgr {
+    /// Whether it's plain text, nothing set.
+    pub fn is_empty(&self) -> bool { *self == Self::default() }
+
+    // takes in the parameters of one SGR sequence, `0` or none start over
+    fn apply(&mut self, params: &str) {
+        let mut params = params.split(';').map(|p| p.parse::<u16>().unwrap_or(0));
+        while let Some(p) = params.next() {
+            match p {
+                0 => *self = Self::default(),
+                21 => self.attributes |= 1 << 3,
+                6 => self.attributes |= 1 << 4,
+                22..=29 => {
+                    for (i, &(_, off)) in ATTRIBUTES.iter().enumerate() {
+                        if off == p {
+                            self.attributes &= !(1 << i);
+                        }
+                    }
+                }
+                30..=37 | 90..=97 => self.fg = Some(Ansi::Basic(p)),
+                40..=47 | 100..=107 => self.bg = Some(Ansi::Basic(p - 10)),
+                39 => self.fg = None,
+                49 => self.bg = None,
+                38 | 48 => {
+                    // an indexed color has one more parameter, a true one three
+                    let mut next = || params.next().unwrap_or(0);
+                    let color = match next() {
+                        5 => Ansi::Indexed(next()),
+                        2 => Ansi::Rgb(next(), next(), next()),
+                        _ => continue,
+                    };
+                    if p == 38 {
+                        self.fg = Some(color);
+                    } else {
+                        self.bg = Some(color);
+                    }
+                }
+                _ => {
+                    if let Some(i) = ATTRIBUTES.iter().position(|a| a.0 == p) {
+                        self.attributes |= 1 << i;
+                    }
+                }
+            }
+        }
+    }
+
+    /// The one SGR sequence that turns all of it on, empty for plain text.
+    pub fn on(&self) -> String {
+        let mut codes: Vec<String> = (ATTRIBUTES.iter().enumerate())
+            .filter(|(i, _)| self.attributes & 1 << i != 0)
+            .map(|(_, a)| a.0.to_string())
+            .collect();
+        codes.extend(self.fg.map(|c| c.code(0)));
+        codes.extend(self.bg.map(|c| c.code(10)));
+        sequence(&codes)
+    }
+
+    /// The SGR sequence turning off only what `on` turned on, so whatever
+    /// was set around the span stays.
+    pub fn off(&self) -> String {
+        let mut codes: Vec<String> = Vec::new();
+        for (i, &(_, off)) in ATTRIBUTES.iter().enumerate() {
+            let off = off.to_string();
+            if self.attributes & 1 << i != 0 && !codes.contains(&off) {
+                codes.push(off);
+            }
+        }
+        if self.fg.is_some() {
+            codes.push("39".to_owned());
+        }
+        if self.bg.is_some() {
+            codes.push("49".to_owned());
+        }
+        sequence(&codes)
+    }
+}
+
+impl Ansi {
+    // its SGR parameters, `offset` past the foreground's for backgrounds
+    fn code(self, offset: u16) -> String {
+        match self {
+            Ansi::Basic(c) => (c + offset).to_string(),
+            Ansi::Indexed(n) => format!("{};5;{}", 38 + offset, n),
+            Ansi::Rgb(r, g, b) => format!("{};2;{};{};{}", 38 + offset, r, g, b),
+        }
+    }
+}
+
+fn sequence(codes: &[String]) -> String {
+    if codes.is_empty() {
+        return String::new();
+    }
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+/// How many terminal columns a line takes up.
+pub fn width(line: &[Span]) -> usize { line.iter().map(|s| s.text.width()).sum() }
+
+/// Re-flows each line to at most `max` columns, breaking at spaces where it
+/// can and in the middle of words that are too long to fit on their own.
+pub fn wrap(lines: Vec<Vec<Span>>, max: usize) -> Vec<Vec<Span>> {
+    let max = max.max(1);
+    let mut wrapped = Vec::new();
+    for line in lines {
+        if width(&line) <= max {
+            wrapped.push(line);
+            continue;
+        }
+        let cells: Vec<(char, Sgr)> = line
+            .iter()
+            .flat_map(|s| s.text.chars().map(move |c| (c, s.style)))
+            .collect();
+        let mut start = 0;
+        while start < cells.len() {
+            // the end of the last word that fit, and where the next one starts
+            let mut fits = None;
+            let mut used = 0;
+            let mut end = start;
+            while end < cells.len() {
+                let (c, _) = cells[end];
+                let w = c.width().unwrap_or(0);
+                if used + w > max {
+                    break;
+                }
+                if c == ' ' && end > start {
+                    fits = Some((end, end + 1));
+                }
+                used += w;
+                end += 1;
+            }
+            let (stop, next) = if end == cells.len() {
+                (end, end)
+            } else if cells[end].0 == ' ' {
+                (end, end + 1)
+            } else if let Some(fit) = fits {
+                fit
+            } else if end == start {
+                // a wide character that can't fit even on its own line
+                (end + 1, end + 1)
+            } else {
+                (end, end)
+            };
+            let mut row = Vec::new();
+            let trimmed = cells[start..stop].iter().rposition(|&(c, _)| c != ' ');
+            for &(c, style) in &cells[start..start + trimmed.map_or(0, |t| t + 1)] {
+                push(&mut row, &style, &c.to_string());
+            }
+            wrapped.push(row);
+            start = next;
+            // spaces at the start of a wrapped line would look like indentation
+            while start < cells.len() && cells[start].0 == ' ' {
+                start += 1;
+            }
+        }
+    }
+    wrapped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, wrap, Span};
+
+    // the text of every line, with the spans' styles left out
+    fn plain(lines: &[Vec<Span>]) -> Vec<String> {
+        (lines.iter())
+            .map(|l| l.iter().map(|s| s.text.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn drops_osc_up_to_bel_or_st() {
+        assert_eq!(plain(&parse("d\x1b]0;title\x07e", true)), vec!["de"]);
+        assert_eq!(plain(&parse("d\x1b]0;title\x1b\\e", true)), vec!["de"]);
+        assert_eq!(plain(&parse("d\x1bPdata\x1b\\e", true)), vec!["de"]);
+    }
+
+    #[test]
+    fn drops_other_csi_sequences() {
+        assert_eq!(
+            plain(&parse("a\x1b[2Jb\x1b[10;5Hc\x1b[?25ld", true)),
+            vec!["abcd"]
+        );
+        // colors too, when they're not kept
+        let lines = parse("a\x1b[31mb", false);
+        assert_eq!(plain(&lines), vec!["ab"]);
+        assert!(lines[0].iter().all(|s| s.style.is_empty()));
+    }
+
+    #[test]
+    fn expands_tabs() {
+        assert_eq!(plain(&parse("a\tb", false)), vec!["a       b"]);
+        assert_eq!(
+            plain(&parse("12345678\tb", false)),
+            vec!["12345678        b"]
+        );
+    }
+
+    #[test]
+    fn backspace_erases() {
+        assert_eq!(plain(&parse("abc\x08\x08d", false)), vec!["ad"]);
+        assert_eq!(plain(&parse("\x08a", false)), vec!["a"]);
+    }
+
+    #[test]
+    fn drops_carriage_returns() {
+        assert_eq!(plain(&parse("one\r\ntwo\r\n", false)), vec!["one", "two"]);
+        assert_eq!(plain(&parse("a\rb", false)), vec!["ab"]);
+    }
+
+    // the style of every span on the first line
+    fn styles(text: &str) -> Vec<(String, String)> {
+        let lines = parse(text, true);
+        lines[0]
+            .iter()
+            .map(|s| (s.style.on(), s.style.off()))
+            .collect()
+    }
+
+    #[test]
+    fn collapses_sgr_into_one_sequence() {
+        let rainbow: String = (0..50).map(|n| format!("\x1b[38;5;{}mx", n)).collect();
+        let lines = parse(&rainbow, true);
+        assert_eq!(lines[0].len(), 50);
+        assert_eq!(lines[0][49].style.on(), "\x1b[38;5;49m");
+        assert_eq!(
+            styles("\x1b[1m\x1b[31m\x1b[44ma"),
+            vec![("\x1b[1;31;44m".into(), "\x1b[22;39;49m".into())]
+        );
+    }
+
+    #[test]
+    fn zero_starts_over() {
+        assert_eq!(
+            styles("\x1b[44;1ma\x1b[0;32mb")[1],
+            ("\x1b[32m".into(), "\x1b[39m".into())
+        );
+        let lines = parse("\x1b[31ma\x1b[mb", true);
+        assert!(lines[0][1].style.is_empty());
+        assert_eq!(lines[0][1].style.on(), "");
+    }
+
+    #[test]
+    fn undoes_only_what_was_set() {
+        assert_eq!(styles("\x1b[31ma")[0].1, "\x1b[39m");
+        assert_eq!(styles("\x1b[1;2;4ma")[0].1, "\x1b[22;24m");
+        assert_eq!(
+            styles("\x1b[38;2;1;2;3;48;2;4;5;6ma")[0],
+            ("\x1b[38;2;1;2;3;48;2;4;5;6m".into(), "\x1b[39;49m".into())
+        );
+        assert_eq!(styles("\x1b[1;31m\x1b[22;39ma")[0].0, "");
+    }
+
+    fn wrapped(text: &str, max: usize) -> Vec<String> { plain(&wrap(parse(text, false), max)) }
+
+    #[test]
+    fn wraps_at_spaces() {
+        assert_eq!(
+            wrapped("the quick brown fox", 10),
+            vec!["the quick", "brown fox"]
+        );
+        assert_eq!(
+            wrapped("the quick brown fox", 19),
+            vec!["the quick brown fox"]
+        );
+        assert_eq!(wrapped("one two", 3), vec!["one", "two"]);
+    }
+
+    #[test]
+    fn breaks_long_words() {
+        assert_eq!(wrapped("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(wrapped("a abcdefgh", 4), vec!["a", "abcd", "efgh"]);
+    }
+
+    #[test]
+    fn wide_characters_still_fit() {
+        assert_eq!(wrapped("日本語", 4), vec!["日本", "語"]);
+        // each one gets a line of its own even when it's too wide for it
+        assert_eq!(wrapped("日本", 1), vec!["日", "本"]);
+        assert_eq!(wrapped("日本", 0), vec!["日", "本"]);
+    }
+
+    #[test]
+    fn runs_of_spaces() {
+        assert_eq!(wrapped("one     two", 5), vec!["one", "two"]);
+        assert_eq!(wrapped("one  two  three", 9), vec!["one  two", "three"]);
+        assert_eq!(wrapped("     ", 2), vec![""]);
+    }
+}
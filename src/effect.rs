@@ -0,0 +1,86 @@
+//! Animations to run behind the widgets. The rain is one, anything else that
+//! can step a frame at a time and redraw a cell on demand can be another.
+
+use crate::drawing::{Mask, Rect};
+use crate::rain::Layers;
+use std::io::Write;
+use termion::event::Key;
+
+mod snow;
+pub use snow::Snow;
+
+/// An animation filling part of the screen. It's started with `init`, then
+/// every frame it takes a `step` and gets to `render` what that changed.
+pub trait Effect {
+    /// Starts over on `area`, e.g. the whole terminal.
+    fn init(&mut self, area: Rect);
+    /// Moves the animation on a frame.
+    fn step(&mut self);
+    /// Draws whatever changed since the last render, staying out of the cells
+    /// `forbidden` covers.
+    fn render(&mut self, writer: &mut dyn Write, forbidden: &Mask) -> std::io::Result<()>;
+    /// Draws what belongs at `cell`, for when a widget stops covering it.
+    fn restore(
+        &self,
+        writer: &mut dyn Write,
+        cell: (u16, u16),
+        forbidden: &Mask,
+    ) -> std::io::Result<()>;
+    /// A key the program didn't use itself. Returns whether it was used.
+    fn input(&mut self, _key: Key) -> bool { false }
+    /// The terminal changed size, so the animation needs to fit `area` now.
+    fn resize(&mut self, area: Rect) { self.init(area); }
+}
+
+/// The rain, built by `build` for whatever area it's given.
+pub struct RainEffect {
+    build: Box<dyn Fn(Rect) -> Layers>,
+    rain: Option<Layers>,
+    changed: Vec<(u16, u16)>,
+}
+
+impl RainEffect {
+    /// Rain that `build` makes for the area it's started on.
+    pub fn new(build: impl Fn(Rect) -> Layers + 'static) -> Self {
+        Self {
+            build: Box::new(build),
+            rain: None,
+            changed: Vec::new(),
+        }
+    }
+}
+
+impl Effect for RainEffect {
+    fn init(&mut self, area: Rect) {
+        self.rain = Some((self.build)(area));
+        self.changed.clear();
+    }
+    fn step(&mut self) {
+        if let Some(rain) = &mut self.rain {
+            self.changed.extend(rain.step());
+        }
+    }
+    fn render(&mut self, mut writer: &mut dyn Write, forbidden: &Mask) -> std::io::Result<()> {
+        let rain = match &self.rain {
+            Some(rain) => rain,
+            None => return Ok(()),
+        };
+        for (x, y) in self.changed.drain(..) {
+            if !forbidden.contains(x, y) {
+                rain.restore(&mut writer, (x, y), forbidden)?;
+            }
+        }
+        Ok(())
+    }
+    fn restore(
+        &self,
+        mut writer: &mut dyn Write,
+        cell: (u16, u16),
+        forbidden: &Mask,
+    ) -> std::io::Result<()> {
+        match &self.rain {
+            Some(rain) => rain.restore(&mut writer, cell, forbidden),
+            None => Ok(()),
+        }
+    }
+}
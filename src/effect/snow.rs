@@ -0,0 +1,152 @@
+use super::Effect;
+use crate::drawing::{Mask, Rect};
+use crate::rain::{shade, Brightness};
+use rand::prelude::*;
+use std::io::Write;
+use termion::event::Key;
+use termion::{color, cursor};
+
+const FLAKES: [char; 4] = ['*', '.', '+', 'o'];
+// new flakes per step for every column
+const SNOWFALL: f32 = 0.02;
+
+/// Snowflakes drifting down, swaying from side to side. `+` and `-` make it
+/// snow harder or softer.
+pub struct Snow {
+    area: Rect,
+    color: &'static dyn color::Color,
+    snowfall: f32,
+    flakes: Vec<Flake>,
+    // what's in every cell of the area
+    cells: Vec<Option<char>>,
+    changed: Vec<(u16, u16)>,
+}
+
+struct Flake {
+    x: f32,
+    y: f32,
+    speed: f32,
+    // where it is in its sway
+    phase: f32,
+    c: char,
+}
+
+impl Snow {
+    /// Flakes of `color`, starting with light snowfall.
+    pub fn new(color: &'static dyn color::Color) -> Self {
+        Self {
+            area: Rect::new(1, 1, 0, 0),
+            color,
+            snowfall: SNOWFALL,
+            flakes: Vec::new(),
+            cells: Vec::new(),
+            changed: Vec::new(),
+        }
+    }
+
+    // the cell a flake at (`x`, `y`) is in, counted from the area's corner
+    fn cell(&self, x: f32, y: f32) -> Option<(u16, u16)> {
+        let (x, y) = (x.round(), y.floor());
+        if x < 0.0 || y < 0.0 || x >= self.area.width as f32 || y >= self.area.height as f32 {
+            return None;
+        }
+        Some((self.area.x + x as u16, self.area.y + y as u16))
+    }
+
+    fn index(&self, (x, y): (u16, u16)) -> Option<usize> {
+        if !self.area.contains(x, y) {
+            return None;
+        }
+        Some((y - self.area.y) as usize * self.area.width as usize + (x - self.area.x) as usize)
+    }
+
+    fn set(&mut self, cell: (u16, u16), c: Option<char>) {
+        if let Some(i) = self.index(cell) {
+            self.cells[i] = c;
+            self.changed.push(cell);
+        }
+    }
+}
+
+impl Effect for Snow {
+    fn init(&mut self, area: Rect) {
+        self.area = area;
+        self.flakes.clear();
+        self.cells = vec![None; area.width as usize * area.height as usize];
+        self.changed.clear();
+    }
+    fn step(&mut self) {
+        let mut rng = rand::thread_rng();
+        let mut due = self.area.width as f32 * self.snowfall;
+        while due > 0.0 {
+            if rng.gen::<f32>() < due {
+                self.flakes.push(Flake {
+                    x: rng.gen_range(0.0, self.area.width.max(1) as f32),
+                    y: 0.0,
+                    speed: rng.gen_range(0.1, 0.35),
+                    phase: rng.gen_range(0.0, std::f32::consts::PI * 2.0),
+                    c: *FLAKES.choose(&mut rng).unwrap_or(&'*'),
+                });
+            }
+            due -= 1.0;
+        }
+        let mut flakes = std::mem::take(&mut self.flakes);
+        for flake in &mut flakes {
+            let was = self.cell(flake.x, flake.y);
+            flake.y += flake.speed;
+            flake.phase += 0.15;
+            flake.x += flake.phase.sin() * 0.2;
+            let now = self.cell(flake.x, flake.y);
+            if was != now {
+                if let Some(cell) = was {
+                    self.set(cell, None);
+                }
+                if let Some(cell) = now {
+                    self.set(cell, Some(flake.c));
+                }
+            }
+        }
+        // melted when they reach the bottom
+        let height = self.area.height as f32;
+        flakes.retain(|f| f.y < height);
+        self.flakes = flakes;
+    }
+    fn render(&mut self, writer: &mut dyn Write, forbidden: &Mask) -> std::io::Result<()> {
+        for (x, y) in std::mem::take(&mut self.changed) {
+            if !forbidden.contains(x, y) {
+                self.restore(writer, (x, y), forbidden)?;
+            }
+        }
+        Ok(())
+    }
+    fn restore(
+        &self,
+        writer: &mut dyn Write,
+        cell: (u16, u16),
+        forbidden: &Mask,
+    ) -> std::io::Result<()> {
+        let c = match self.index(cell) {
+            Some(i) => self.cells[i],
+            None => return Ok(()),
+        };
+        let (dim, undim) = shade(Brightness::Normal, forbidden, cell);
+        write!(
+            writer,
+            "{goto}{dim}{color}{c}{reset}{undim}",
+            goto = cursor::Goto(cell.0, cell.1),
+            dim = dim,
+            color = color::Fg(self.color),
+            c = c.unwrap_or(' '),
+            reset = color::Fg(color::Reset),
+            undim = undim,
+        )
+    }
+    fn input(&mut self, key: Key) -> bool {
+        match key {
+            Key::Char('+') => self.snowfall = (self.snowfall * 1.5).min(1.0),
+            Key::Char('-') => self.snowfall /= 1.5,
+            _ => return false,
+        }
+        true
+    }
+}
@@ -0,0 +1,20 @@
+//! The Matrix rain from `rmatrix`, and the widgets it draws over it, for
+//! use in other terminal programs.
+//!
+//! `rain::Rain` writes the falling characters straight to a termion writer,
+//! a step at a time. Widgets from `drawing` are drawn on top wherever the
+//! `drawing::Placer` puts them, and the rain is kept out of what they cover
+//! with a `drawing::Mask`. `screen::Screen` does that bookkeeping for a
+//! whole frame of widgets over any `effect::Effect`.
+
+extern crate chrono;
+extern crate libc;
+extern crate rand;
+extern crate termion;
+extern crate unicode_width;
+
+pub mod drawing;
+pub mod effect;
+pub mod rain;
+pub mod runner;
+pub mod screen;
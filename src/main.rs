@@ -1,24 +1,37 @@
 extern crate chrono;
+extern crate chrono_tz;
 extern crate clap;
-extern crate rand;
+extern crate rmatrix;
 extern crate termion;
-use chrono::Local;
-mod drawing;
-use clap::{App, Arg};
-use drawing::{Blank, Draw, Frame, Label, Numbers};
-use rand::{distributions::Uniform, prelude::*};
-use std::collections::HashSet;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{Local, Utc};
+use chrono_tz::Tz;
+mod quote;
+mod timer;
+use clap::{App, Arg, ArgGroup};
+use quote::QuoteSource;
+use rmatrix::drawing::{
+    Align, Aligned, Anchor, Border, Cached, Calendar, Colossal, Doom, Draw, FigletFont, Font,
+    Frame, Label, Numbers, Rect, Size, Style, VStack,
+};
+use rmatrix::effect::{Effect, RainEffect, Snow};
+use rmatrix::rain::Layers;
+use rmatrix::runner::CommandRunner;
+use rmatrix::screen::Screen;
 use std::io::{stdout, Write};
+use std::time::Instant;
 use std::{thread, time};
 use termion::event::Key;
 use termion::raw::IntoRawMode;
 use termion::{async_stdin, input::TermRead};
 use termion::{clear, color, cursor};
-
-const LENGTH_OF_CLOCK: u16 = 75;
+use timer::{Mode, Timer};
 
 fn main() -> std::io::Result<()> {
     static COLORS_AVAILABLE: &str = "Colors available: red, green, blue, yellow, magenta";
+    static ANCHORS_AVAILABLE: &str = "Anchors available: top-left, top, top-right, left, center, \
+                                      right, bottom-left, bottom, bottom-right";
+    static OFFSET_HELP: &str = "Cells in from the anchored edges, as x,y";
 
     let command_args = App::new("rmatrix")
         .version("0.9")
@@ -55,21 +68,231 @@ fn main() -> std::io::Result<()> {
                 .long("horizontal")
                 .takes_value(false),
         )
+        .arg(
+            Arg::with_name("effect")
+                .long("effect")
+                .help("Animation behind everything: rain or snow (default rain)")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("layers")
+                .long("layers")
+                .help("Layers of rain at different depths, far ones dim and slow (default 1)")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("wind")
+                .long("wind")
+                .help("Blows the rain sideways, in cells per cell fallen, e.g. 0.5 or -1")
+                .allow_hyphen_values(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("gravity")
+                .long("gravity")
+                .help("Drops speed up as they fall, by this many cells per step, e.g. 0.05")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("terminal_velocity")
+                .long("terminal-velocity")
+                .help("The fastest drops fall with --gravity, in cells per step")
+                .requires("gravity")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("splash")
+                .long("splash")
+                .help("Drops splash when they hit the bottom"),
+        )
         .arg(
             Arg::with_name("clock")
                 .short("c")
                 .long("clock")
-                .takes_value(false),
+                .help("Shows a clock, repeat with time zones (local, UTC, America/New_York, ...)")
+                .takes_value(true)
+                .multiple(true)
+                .min_values(0),
+        )
+        .arg(
+            Arg::with_name("clock_anchor")
+                .long("clock-anchor")
+                .help(ANCHORS_AVAILABLE)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("clock_offset")
+                .long("clock-offset")
+                .help(OFFSET_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("countdown")
+                .long("countdown")
+                .help("Counts down from a duration like 25m, 90s or 1h30m")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("until")
+                .long("until")
+                .help("Counts down to a time of day like 17:00")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("stopwatch")
+                .long("stopwatch")
+                .help("Counts up from zero"),
         )
+        .arg(
+            Arg::with_name("pomodoro")
+                .long("pomodoro")
+                .help("Alternates work and break countdowns, as work/break (default 25m/5m)")
+                .takes_value(true)
+                .min_values(0)
+                .max_values(1),
+        )
+        .group(ArgGroup::with_name("timer").args(&["countdown", "until", "stopwatch", "pomodoro"]))
+        .arg(
+            Arg::with_name("timer_anchor")
+                .long("timer-anchor")
+                .help(ANCHORS_AVAILABLE)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("timer_offset")
+                .long("timer-offset")
+                .help(OFFSET_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("date")
+                .long("date")
+                .help("Shows the date in big text, optionally with a strftime format")
+                .takes_value(true)
+                .min_values(0)
+                .max_values(1),
+        )
+        .arg(
+            Arg::with_name("date_anchor")
+                .long("date-anchor")
+                .help(ANCHORS_AVAILABLE)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("date_offset")
+                .long("date-offset")
+                .help(OFFSET_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("calendar")
+                .long("calendar")
+                .help("Shows this month's calendar"),
+        )
+        .arg(
+            Arg::with_name("calendar_anchor")
+                .long("calendar-anchor")
+                .help(ANCHORS_AVAILABLE)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("calendar_offset")
+                .long("calendar-offset")
+                .help(OFFSET_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("font")
+                .long("font")
+                .help("Fonts available: doom, colossal")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("font_file")
+                .long("font-file")
+                .help("FIGlet .flf font used for the clock, overrides --font")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("border")
+                .long("border")
+                .help("Frame style: single, double, rounded, heavy, ascii or any one character")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("border_color")
+                .long("border-color")
+                .help(COLORS_AVAILABLE)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("panel_color")
+                .long("panel-color")
+                .help("Fills frames with a background color, see --border-color")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("text_color")
+                .long("text-color")
+                .help("Color for clock digits, quotes and the calendar, see --border-color")
+                .takes_value(true),
+        )
+        .arg(Arg::with_name("see_through").long("see-through").help(
+            "Lets the rain fall, dimmed, through the empty cells of widgets without a panel color",
+        ))
         .arg(
             Arg::with_name("fortune")
                 .long("fortune")
                 .short("f")
                 .help("Displays fortune quotes (needs fortune in $PATH)"),
         )
+        .arg(
+            Arg::with_name("quote_cmd")
+                .long("quote-cmd")
+                .help("Displays the output of a shell command as the quote")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("quote_file")
+                .long("quote-file")
+                .help("Displays random quotes from a file, separated by blank lines")
+                .takes_value(true),
+        )
+        .group(ArgGroup::with_name("quotes").args(&["fortune", "quote_cmd", "quote_file"]))
+        .arg(
+            Arg::with_name("quote_interval")
+                .long("quote-interval")
+                .help("Seconds between quotes (default 5)")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("quote_colors")
+                .long("quote-colors")
+                .help("Keeps the colors in quotes instead of stripping them, e.g. for lolcat"),
+        )
+        .arg(
+            Arg::with_name("quote_align")
+                .long("quote-align")
+                .help("Aligns quote lines: left, center or right (default left)")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("quote_anchor")
+                .long("quote-anchor")
+                .alias("fortune-anchor")
+                .help(ANCHORS_AVAILABLE)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("quote_offset")
+                .long("quote-offset")
+                .alias("fortune-offset")
+                .help(OFFSET_HELP)
+                .takes_value(true),
+        )
         .get_matches();
     // variable setup
-    let end_color: &dyn color::Color = match &command_args.args.get("end_color") {
+    let end_color: &'static dyn color::Color = match &command_args.args.get("end_color") {
         Some(a) => match get_color(a.vals[0].clone()) {
             Some(c) => c,
             None => {
@@ -79,7 +302,7 @@ fn main() -> std::io::Result<()> {
         },
         None => &color::White,
     };
-    let main_color: &dyn color::Color = match &command_args.args.get("main_color") {
+    let main_color: &'static dyn color::Color = match &command_args.args.get("main_color") {
         Some(a) => match get_color(a.vals[0].clone()) {
             Some(c) => c,
             None => {
@@ -90,25 +313,268 @@ fn main() -> std::io::Result<()> {
         None => &color::Blue,
     };
 
-    let reverse = command_args.args.get("reverse").is_some();
-    let horizontal = command_args.args.get("horizontal").is_some();
-    let clock = command_args.args.get("clock").is_some();
-    let fortune = command_args.args.get("fortune").is_some();
+    let reverse = command_args.args.contains_key("reverse");
+    let horizontal = command_args.args.contains_key("horizontal");
+    let layers = match command_args.value_of("layers").map(str::parse::<u16>) {
+        Some(Ok(n)) if (1..=8).contains(&n) => n,
+        Some(_) => {
+            println!("Bad number of layers, 1 to 8, see --help");
+            return Ok(());
+        }
+        None => 1,
+    };
+    let wind = match command_args.value_of("wind").map(str::parse::<f32>) {
+        Some(Ok(w)) if (-4.0..=4.0).contains(&w) => w,
+        Some(_) => {
+            println!("Bad wind, -4 to 4, see --help");
+            return Ok(());
+        }
+        None => 0.0,
+    };
+    let gravity = match command_args.value_of("gravity").map(str::parse::<f32>) {
+        Some(Ok(g)) if (0.0..=1.0).contains(&g) => g,
+        Some(_) => {
+            println!("Bad gravity, 0 to 1, see --help");
+            return Ok(());
+        }
+        None => 0.0,
+    };
+    let terminal_velocity = match command_args.value_of("terminal_velocity").map(str::parse) {
+        Some(Ok(v)) if v > 0.0 => Some(v),
+        Some(_) => {
+            println!("Bad terminal velocity, see --help");
+            return Ok(());
+        }
+        None => None,
+    };
+    let splash = command_args.is_present("splash");
+    let effect: Box<dyn Effect> = match command_args.value_of("effect").unwrap_or("rain") {
+        "rain" => Box::new(RainEffect::new(move |area| {
+            Layers::depth(layers, area, reverse, horizontal)
+                .with_wind(wind)
+                .with_gravity(gravity, terminal_velocity)
+                .with_splash(splash)
+                .with_colors(main_color, end_color)
+        })),
+        "snow" => Box::new(Snow::new(end_color)),
+        _ => {
+            println!("Unknown effect, see --help");
+            return Ok(());
+        }
+    };
+    let mut clocks = Vec::new();
+    if command_args.is_present("clock") {
+        let zones: Vec<&str> = command_args
+            .values_of("clock")
+            .into_iter()
+            .flatten()
+            .collect();
+        for name in &zones {
+            let zone = if *name == "local" {
+                None
+            } else {
+                match name.parse::<Tz>() {
+                    Ok(tz) => Some(tz),
+                    Err(_) => {
+                        println!("Unknown time zone {}, see --help", name);
+                        return Ok(());
+                    }
+                }
+            };
+            clocks.push(WorldClock {
+                zone,
+                caption: Some((*name).to_owned()),
+            });
+        }
+        // a bare --clock is the local time, without a caption
+        if zones.is_empty() {
+            clocks.push(WorldClock {
+                zone: None,
+                caption: None,
+            });
+        }
+    }
+    let quotes = if let Some(path) = command_args.value_of("quote_file") {
+        match QuoteSource::from_file(path) {
+            Ok(q) => Some(q),
+            Err(e) => {
+                println!("Bad quote file {}: {}", path, e);
+                return Ok(());
+            }
+        }
+    } else if let Some(cmd) = command_args.value_of("quote_cmd") {
+        Some(QuoteSource::Command(CommandRunner::new(
+            cmd,
+            COMMAND_TIMEOUT,
+        )))
+    } else if command_args.is_present("fortune") {
+        Some(QuoteSource::Command(CommandRunner::new(
+            "fortune",
+            COMMAND_TIMEOUT,
+        )))
+    } else {
+        None
+    };
+    let quote_interval = match command_args
+        .value_of("quote_interval")
+        .map(str::parse::<f32>)
+    {
+        Some(Ok(secs)) if secs > 0.0 => time::Duration::from_secs_f32(secs),
+        Some(_) => {
+            println!("Bad quote interval, see --help");
+            return Ok(());
+        }
+        None => time::Duration::from_secs(5),
+    };
+    let date_format = if command_args.is_present("date") {
+        let format = command_args.value_of("date").unwrap_or("%a %b %d");
+        if StrftimeItems::new(format).any(|i| i == Item::Error) {
+            println!("Bad date format, see --help");
+            return Ok(());
+        }
+        Some(format.to_owned())
+    } else {
+        None
+    };
+    let calendar = command_args.is_present("calendar");
+    let mode = if let Some(d) = command_args.value_of("countdown") {
+        match timer::parse_duration(d) {
+            Some(d) => Some(Mode::Countdown(d)),
+            None => {
+                println!("Bad countdown duration, see --help");
+                return Ok(());
+            }
+        }
+    } else if let Some(t) = command_args.value_of("until") {
+        match timer::parse_time(t) {
+            Some(t) => Some(Mode::Until(t)),
+            None => {
+                println!("Bad --until time, see --help");
+                return Ok(());
+            }
+        }
+    } else if command_args.is_present("stopwatch") {
+        Some(Mode::Stopwatch)
+    } else if command_args.is_present("pomodoro") {
+        let phases = command_args.value_of("pomodoro").unwrap_or("25m/5m");
+        let mut phases = phases.split('/').map(timer::parse_duration);
+        match (phases.next(), phases.next(), phases.next()) {
+            (Some(Some(work)), Some(Some(rest)), None) => Some(Mode::Pomodoro { work, rest }),
+            _ => {
+                println!("Bad pomodoro phases, see --help");
+                return Ok(());
+            }
+        }
+    } else {
+        None
+    };
+    let font: Box<dyn Font> = match command_args.value_of("font_file") {
+        Some(path) => match FigletFont::load(path) {
+            Ok(f) => Box::new(f),
+            Err(e) => {
+                println!("Bad font file {}: {}", path, e);
+                return Ok(());
+            }
+        },
+        None => match command_args.value_of("font").unwrap_or("doom") {
+            "doom" => Box::new(Doom),
+            "colossal" => Box::new(Colossal),
+            _ => {
+                println!("Bad font, see --help");
+                return Ok(());
+            }
+        },
+    };
+    let clock_placement = match get_placement(&command_args, "clock", Anchor::BottomLeft, (10, 4)) {
+        Some(p) => p,
+        None => return Ok(()),
+    };
+    let quote_placement = match get_placement(&command_args, "quote", Anchor::TopRight, (10, 4)) {
+        Some(p) => p,
+        None => return Ok(()),
+    };
+    let border = match command_args.value_of("border").map(get_border) {
+        Some(Some(border)) => border,
+        Some(None) => {
+            println!("Bad border, see --help");
+            return Ok(());
+        }
+        None => Border::Solid('#'),
+    };
+    let border_style = Style {
+        fg: match get_style_color(&command_args, "border") {
+            Some(c) => c,
+            None => return Ok(()),
+        },
+        bg: match get_style_color(&command_args, "panel") {
+            Some(c) => c,
+            None => return Ok(()),
+        },
+        ..Style::default()
+    };
+    let text_style = Style {
+        fg: match get_style_color(&command_args, "text") {
+            Some(c) => c,
+            None => return Ok(()),
+        },
+        ..Style::default()
+    };
+    let quote_align = match command_args.value_of("quote_align").map(get_align) {
+        Some(Some(align)) => align,
+        Some(None) => {
+            println!("Bad quote alignment, see --help");
+            return Ok(());
+        }
+        None => Align::Left,
+    };
+    let timer_placement = match get_placement(&command_args, "timer", Anchor::Center, (0, 0)) {
+        Some(p) => p,
+        None => return Ok(()),
+    };
+    let date_placement = match get_placement(&command_args, "date", Anchor::TopLeft, (10, 4)) {
+        Some(p) => p,
+        None => return Ok(()),
+    };
+    let calendar_placement =
+        match get_placement(&command_args, "calendar", Anchor::BottomRight, (10, 4)) {
+            Some(p) => p,
+            None => return Ok(()),
+        };
+    let settings = Settings {
+        horizontal,
+        clocks,
+        date_format,
+        calendar,
+        quote_interval,
+        quote_colors: command_args.is_present("quote_colors"),
+        quote_align,
+        border,
+        border_style,
+        text_style,
+        see_through: command_args.is_present("see_through"),
+        clock_width: font.clock_size(),
+        font,
+        clock_placement,
+        quote_placement,
+        timer_placement,
+        date_placement,
+        calendar_placement,
+    };
+    // kept outside `hot_loop` so resizing doesn't restart it
+    let mut state = State {
+        timer: mode.map(Timer::from),
+        quotes,
+        quote: None,
+        next_quote: Instant::now(),
+        effect,
+        effect_started: false,
+    };
     // main loop
     let mut stdout = stdout().into_raw_mode().unwrap();
     let mut stdin = async_stdin().keys();
 
     loop {
-        match hot_loop(
-            &mut stdin,
-            &mut stdout,
-            &main_color,
-            &end_color,
-            reverse,
-            horizontal,
-            clock,
-            fortune,
-        )? {
+        match hot_loop(&mut stdin, &mut stdout, &settings, &mut state)? {
             ExitReason::Quite => break,
             ExitReason::SizeChange => thread::sleep(time::Duration::from_secs_f32(0.1)),
         }
@@ -117,7 +583,8 @@ fn main() -> std::io::Result<()> {
     // cleanup, needed to return to normal state
     write!(
         stdout,
-        "{goto}{clear}{show_cursor}",
+        "{flash}{goto}{clear}{show_cursor}",
+        flash = FLASH_OFF,
         clear = clear::All,
         goto = cursor::Goto(1, 1),
         show_cursor = cursor::Show
@@ -130,39 +597,113 @@ enum ExitReason {
     SizeChange,
 }
 
+struct Settings {
+    horizontal: bool,
+    clocks: Vec<WorldClock>,
+    date_format: Option<String>,
+    calendar: bool,
+    quote_interval: time::Duration,
+    quote_colors: bool,
+    quote_align: Align,
+    border: Border,
+    border_style: Style,
+    text_style: Style,
+    see_through: bool,
+    font: Box<dyn Font>,
+    clock_width: u16,
+    clock_placement: Placement,
+    quote_placement: Placement,
+    timer_placement: Placement,
+    date_placement: Placement,
+    calendar_placement: Placement,
+}
+
+// Everything that changes while running and should survive a resize.
+struct State {
+    timer: Option<Timer>,
+    quotes: Option<QuoteSource>,
+    quote: Option<String>,
+    next_quote: Instant,
+    effect: Box<dyn Effect>,
+    // whether the effect was set up before, the next time it's resized
+    effect_started: bool,
+}
+
+struct WorldClock {
+    // `None` is the local time zone
+    zone: Option<Tz>,
+    caption: Option<String>,
+}
+
+impl WorldClock {
+    fn now(&self) -> String {
+        const FORMAT: &str = "%I:%M:%S%p";
+        match self.zone {
+            Some(tz) => Utc::now().with_timezone(&tz).format(FORMAT).to_string(),
+            None => Local::now().format(FORMAT).to_string(),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Placement {
+    anchor: Anchor,
+    offset: (u16, u16),
+}
+
+// Reverse video for the whole screen, used to flash when a timer runs out.
+const FLASH_ON: &str = "\x1b[?5h";
+const FLASH_OFF: &str = "\x1b[?5l";
+const FLASH_FRAMES: u16 = 6;
+
+// How long a quote command gets before it's killed.
+const COMMAND_TIMEOUT: time::Duration = time::Duration::from_secs(10);
+
+// `widget` in the middle of a row `width` wide, for captions under frames.
+fn centered(widget: Label, width: u16) -> Box<dyn Draw> {
+    let size = Size::new(widget.size().rows(), width.max(widget.size().width()));
+    Box::new(Aligned::from(Box::new(widget), Anchor::Top, size))
+}
+
+// `widget` in the border picked on the command line.
+fn bordered(widget: Box<dyn Draw>, settings: &Settings) -> Frame {
+    Frame::from(' ', widget)
+        .with_border(settings.border)
+        .with_style(settings.border_style)
+}
+
+// Big digits with a blank ring between them and the border, like the clock.
+fn framed_numbers(text: &str, min_width: u16, settings: &Settings) -> Frame {
+    let numbers = Numbers::from(text, settings.font.as_ref())
+        .with_min_width(min_width)
+        .with_style(settings.text_style);
+    bordered(Box::new(Frame::from(' ', Box::new(numbers))), settings)
+}
+
 fn hot_loop(
     mut stdin: &mut termion::input::Keys<termion::AsyncReader>,
     mut stdout: &mut dyn Write,
-    main_color: &dyn color::Color,
-    second_color: &dyn color::Color,
-    reverse: bool,
-    horizontal: bool,
-    clock: bool,
-    fortune: bool,
+    settings: &Settings,
+    state: &mut State,
 ) -> std::io::Result<ExitReason> {
+    let horizontal = settings.horizontal;
     let (x_size, y_size) = termion::terminal_size()?;
     write!(stdout, "{}{}", cursor::Hide, clear::All)?;
-    let mut columns: Vec<Column> = if !horizontal {
-        1..x_size
+    let area = Rect::new(1, 1, x_size, y_size);
+    // we only get here again when the size changed, with the screen cleared
+    if !state.effect_started {
+        state.effect.init(area);
     } else {
-        0..y_size + 1
+        state.effect.resize(area);
     }
-    .into_iter()
-    .map(|c| {
-        Column::new(
-            c,
-            if !horizontal { y_size } else { x_size },
-            reverse,
-            horizontal,
-        )
-    })
-    .collect();
-    let mut loop_time = 0;
-    let mut quote: Option<String> = if fortune {
-        Some("foo".to_owned())
-    } else {
-        None
-    };
+    state.effect_started = true;
+    let mut flash = 0;
+    let mut screen = Screen::new((x_size, y_size), settings.see_through);
+    let mut clock_widget = Cached::new();
+    let mut date_widget = Cached::new();
+    let mut calendar_widget = Cached::new();
+    let mut timer_widget = Cached::new();
+    let mut quote_widget = Cached::new();
     // main loop initialize
     write!(stdout, "{}{}", cursor::Hide, clear::All)?;
     // main loop
@@ -173,276 +714,241 @@ fn hot_loop(
         for c in &mut stdin {
             match c.unwrap() {
                 Key::Char('q') => return Ok(ExitReason::Quite),
-                _ => {}
+                Key::Char(' ') => state.timer.iter_mut().for_each(Timer::toggle),
+                Key::Char('r') => state.timer.iter_mut().for_each(Timer::reset),
+                key => {
+                    state.effect.input(key);
+                }
             }
         }
-        let mut forbidden = HashSet::new();
-        if clock {
-            let t = format!("{}", Local::now().format("%I:%M:%S%p"));
-            let now_clock = Frame::from(
-                '#',
-                Box::new(Frame::from(
-                    ' ',
-                    Box::new(Numbers::from(&t).with_min_width(LENGTH_OF_CLOCK)),
-                )),
-            );
-            let clock_size = now_clock.size();
-            forbidden.extend(now_clock.draw(&mut stdout, 10, y_size - clock_size.height() - 4)?);
-        }
-        if let Some(q) = quote.as_mut() {
-            const Y_DOWN: u16 = 5;
-            if loop_time == 0 {
-                let quote = std::process::Command::new("fortune").output()?;
-                if quote.status.success() {
-                    let old = Blank::from(
-                        Frame::from('#', Box::new(Frame::from(' ', Box::new(Label::from(&q)))))
-                            .size(),
-                    );
-                    forbidden.extend(old.draw(
-                        &mut stdout,
-                        x_size - old.size().width() - 10,
-                        Y_DOWN,
-                    )?);
-                    *q = String::from_utf8_lossy(&quote.stdout).to_string();
+        // earlier widgets get first pick of where to go
+        if !settings.clocks.is_empty() {
+            let times: Vec<String> = settings.clocks.iter().map(WorldClock::now).collect();
+            clock_widget.update(&times.concat(), || {
+                // every clock stacked above its caption
+                let mut stack: Vec<Box<dyn Draw>> = Vec::new();
+                for (clock, time) in settings.clocks.iter().zip(&times) {
+                    let numbers = framed_numbers(time, settings.clock_width, settings);
+                    let width = numbers.size().width();
+                    stack.push(Box::new(numbers));
+                    if let Some(caption) = &clock.caption {
+                        let caption = Label::from(caption).with_style(settings.text_style);
+                        stack.push(centered(caption, width));
+                    }
                 }
-                let new = Blank::from(
-                    Frame::from('#', Box::new(Frame::from(' ', Box::new(Label::from(&q))))).size(),
-                );
-                forbidden.extend(new.draw(
-                    &mut stdout,
-                    x_size - new.size().width() - 10,
-                    Y_DOWN,
-                )?);
-            } else {
-                let label = Frame::from('#', Box::new(Frame::from(' ', Box::new(Label::from(&q)))));
-                forbidden.extend(label.draw(
+                Box::new(VStack::from(stack))
+            });
+            screen.draw(
+                &mut stdout,
+                &mut clock_widget,
+                settings.clock_placement.anchor,
+                settings.clock_placement.offset,
+            )?;
+        }
+        if let Some(format) = &settings.date_format {
+            let date = Local::now().format(format).to_string();
+            date_widget.update(&date, || Box::new(framed_numbers(&date, 0, settings)));
+            screen.draw(
+                &mut stdout,
+                &mut date_widget,
+                settings.date_placement.anchor,
+                settings.date_placement.offset,
+            )?;
+        }
+        if settings.calendar {
+            let today = Local::now().naive_local().date();
+            calendar_widget.update(&today.to_string(), || {
+                let calendar = Calendar::from(today).with_style(settings.text_style);
+                Box::new(bordered(
+                    Box::new(Frame::from(' ', Box::new(calendar))),
+                    settings,
+                ))
+            });
+            screen.draw(
+                &mut stdout,
+                &mut calendar_widget,
+                settings.calendar_placement.anchor,
+                settings.calendar_placement.offset,
+            )?;
+        }
+        if let Some(timer) = state.timer.as_mut() {
+            if timer.tick() {
+                write!(stdout, "\x07{}", FLASH_ON)?;
+                flash = FLASH_FRAMES;
+            }
+            let text = timer.display();
+            let caption = timer.caption().unwrap_or("");
+            timer_widget.update(&format!("{} {}", text, caption), || {
+                // as wide as the widest digits could make it, so it doesn't jitter
+                let width = (b'0'..=b'9')
+                    .map(|d| text.replace(|c: char| c.is_ascii_digit(), &(d as char).to_string()))
+                    .map(|t| Numbers::from(&t, settings.font.as_ref()).size().width())
+                    .max()
+                    .unwrap_or(0);
+                let numbers = framed_numbers(&text, width, settings);
+                // an empty caption keeps the digits from moving when one shows up
+                let caption = Label::from(caption).with_style(settings.text_style);
+                let caption = centered(caption, numbers.size().width());
+                Box::new(VStack::from(vec![Box::new(numbers), caption]))
+            });
+            screen.draw(
+                &mut stdout,
+                &mut timer_widget,
+                settings.timer_placement.anchor,
+                settings.timer_placement.offset,
+            )?;
+        }
+        if flash > 0 {
+            flash -= 1;
+            if flash == 0 {
+                write!(stdout, "{}", FLASH_OFF)?;
+            }
+        }
+        if let Some(source) = state.quotes.as_mut() {
+            let boxed = |q: &str| {
+                let label = if settings.quote_colors {
+                    Label::styled(q)
+                } else {
+                    Label::from(q)
+                };
+                // the frames and margin take up the rest of the screen
+                let taken = settings.quote_placement.offset.0.saturating_add(6);
+                let fit = x_size.saturating_sub(taken);
+                // the panel's background too, for after text that sets its own
+                let style = Style {
+                    bg: settings.border_style.bg,
+                    ..settings.text_style
+                };
+                let label = label
+                    .with_style(style)
+                    .with_max_width(fit.max(10))
+                    .with_align(settings.quote_align)
+                    .with_padding(1, 1);
+                bordered(Box::new(label), settings)
+            };
+            if Instant::now() >= state.next_quote {
+                // the command runs in the background, the old quote stays until it's done
+                source.request();
+                state.next_quote = Instant::now() + settings.quote_interval;
+            }
+            if let Some(q) = source.poll() {
+                state.quote = Some(q);
+            }
+            if let Some(q) = &state.quote {
+                quote_widget.update(q, || Box::new(boxed(q)));
+                screen.draw(
                     &mut stdout,
-                    x_size - label.size().width() - 10,
-                    Y_DOWN,
-                )?);
+                    &mut quote_widget,
+                    settings.quote_placement.anchor,
+                    settings.quote_placement.offset,
+                )?;
             }
         }
-
+        screen.finish(stdout, state.effect.as_ref())?;
         stdout.flush()?;
         thread::sleep(time::Duration::from_secs_f32(
             if horizontal { 0.5 } else { 1.0 } * 0.05,
         ));
-        loop_time += 1;
-        loop_time = loop_time % 100;
 
-        for c in &mut columns {
-            c.update(&mut stdout, main_color, second_color, &forbidden)?;
-        }
+        state.effect.step();
+        state.effect.render(stdout, screen.mask())?;
     }
 }
 
-struct Column {
-    start: u16,
-    end: u16,
-    max_height: u16,
-    column: u16,
-    last_made: char,
-    delay: u16,
-    reverse: bool,
-    horizontal: bool,
-}
-
-impl Column {
-    fn new(column: u16, max_height: u16, rev: bool, horizontal: bool) -> Self {
-        Self {
-            start: if !rev {
-                0 + horizontal as u16
-            } else {
-                max_height
-            },
-            end: if !rev {
-                0 + horizontal as u16
-            } else {
-                max_height
-            },
-            max_height: max_height,
-            column: column,
-            last_made: ' ',
-            delay: Uniform::new_inclusive(0, if !horizontal { 150 } else { 300 })
-                .sample(&mut rand::thread_rng()),
-            reverse: rev,
-            horizontal: horizontal,
-        }
-    }
-    fn update<T: Write>(
-        &mut self,
-        writer: &mut T,
-        c1: &dyn color::Color,
-        c2: &dyn color::Color,
-        forbidden: &HashSet<(u16, u16)>,
-    ) -> std::io::Result<()> {
-        if self.delay == 0 {
-            let action = Uniform::new_inclusive(0, 2).sample(&mut rand::thread_rng());
-            let lowest = if !self.horizontal { 0 } else { 1 };
-            if (!self.reverse && self.max_height != self.end && self.max_height != self.start)
-                || (self.reverse && lowest != self.end && lowest != self.start)
-            {
-                if action == 1 || action == 2 {
-                    self.add_last_char(writer, c1, c2, forbidden)?;
-                }
-                if action == 2 || action == 3 {
-                    self.delete_first_char(writer, forbidden)?;
-                }
-            } else if ((!self.reverse && self.max_height == self.end)
-                || (self.reverse && lowest == self.end))
-                && self.end == self.start
-            {
-                // finished column
-                let new = if !self.reverse {
-                    lowest
-                } else {
-                    self.max_height
-                };
-                self.start = new;
-                self.end = new;
-                self.delay = Uniform::new_inclusive(0, 100).sample(&mut rand::thread_rng());
-            } else if (!self.reverse && self.max_height == self.end)
-                || (self.reverse && lowest == self.end)
-            {
-                // finishing up column
-                self.delete_first_char(writer, forbidden)?;
-                self.fix_last_char(writer, c1, forbidden)?;
+// Reads `--<name>-color`, complaining about bad values. `Some(None)` if unset.
+fn get_style_color(
+    args: &clap::ArgMatches,
+    name: &str,
+) -> Option<Option<&'static dyn color::Color>> {
+    match args.args.get(&*format!("{}_color", name)) {
+        Some(arg) => match get_color(arg.vals[0].clone()) {
+            Some(c) => Some(Some(c)),
+            None => {
+                println!("Bad {} color, see --help", name);
+                None
             }
-        } else {
-            self.delay -= 1;
-            self.delete_last_char(writer, forbidden)?;
-        }
-
-        Ok(())
+        },
+        None => Some(None),
     }
+}
 
-    fn delete_first_char<T: Write>(
-        &mut self,
-        writer: &mut T,
-        forbidden: &HashSet<(u16, u16)>,
-    ) -> std::io::Result<()> {
-        // delete last char created
-        let pair = if !self.horizontal {
-            (self.column, self.start)
-        } else {
-            (self.start, self.column)
-        };
-        if forbidden.contains(&pair) {
-        } else {
-            write!(
-                writer,
-                "{goto}{space}",
-                goto = cursor::Goto(pair.0, pair.1),
-                space = " "
-            )?;
-        }
-        if !self.reverse {
-            self.start += 1;
-        } else {
-            self.start -= 1;
-        };
-        Ok(())
-    }
+// Reads `--<name>-anchor` and `--<name>-offset`, complaining about bad values.
+fn get_placement(
+    args: &clap::ArgMatches,
+    name: &str,
+    anchor: Anchor,
+    offset: (u16, u16),
+) -> Option<Placement> {
+    let anchor = match args.value_of(format!("{}_anchor", name)) {
+        Some(a) => match get_anchor(a) {
+            Some(a) => a,
+            None => {
+                println!("Bad {} anchor, see --help", name);
+                return None;
+            }
+        },
+        None => anchor,
+    };
+    let offset = match args.value_of(format!("{}_offset", name)) {
+        Some(o) => match get_offset(o) {
+            Some(o) => o,
+            None => {
+                println!("Bad {} offset, see --help", name);
+                return None;
+            }
+        },
+        None => offset,
+    };
+    Some(Placement { anchor, offset })
+}
 
-    fn delete_last_char<T: Write>(
-        &mut self,
-        writer: &mut T,
-        forbidden: &HashSet<(u16, u16)>,
-    ) -> std::io::Result<()> {
-        let lowest = if self.horizontal { 1 } else { 0 };
-        let pair = if !self.horizontal {
-            (
-                self.column,
-                if !self.reverse {
-                    self.max_height
-                } else {
-                    lowest
-                },
-            )
-        } else {
-            (
-                if !self.reverse {
-                    self.max_height
-                } else {
-                    lowest
-                },
-                self.column,
-            )
-        };
-        if forbidden.contains(&pair) {
-        } else {
-            write!(writer, "{goto} ", goto = cursor::Goto(pair.0, pair.1))?;
-        }
-        Ok(())
+fn get_anchor(name: &str) -> Option<Anchor> {
+    match name {
+        "top-left" => Some(Anchor::TopLeft),
+        "top" => Some(Anchor::Top),
+        "top-right" => Some(Anchor::TopRight),
+        "left" => Some(Anchor::Left),
+        "center" => Some(Anchor::Center),
+        "right" => Some(Anchor::Right),
+        "bottom-left" => Some(Anchor::BottomLeft),
+        "bottom" => Some(Anchor::Bottom),
+        "bottom-right" => Some(Anchor::BottomRight),
+        _ => None,
     }
+}
 
-    fn add_last_char<T: Write>(
-        &mut self,
-        writer: &mut T,
-        c1: &dyn color::Color,
-        c2: &dyn color::Color,
-        forbidden: &HashSet<(u16, u16)>,
-    ) -> std::io::Result<()> {
-        // fix color of old char
-        self.fix_last_char(writer, c1, forbidden)?;
-        if self.reverse == false {
-            self.end += 1;
-        } else {
-            self.end -= 1;
-        };
-        let pair = if !self.horizontal {
-            (self.column, self.end)
-        } else {
-            (self.end, self.column)
-        };
-        if forbidden.contains(&pair) {
-        } else {
-            // create new char at end
-            self.last_made = random_char();
-            write!(
-                writer,
-                "{color}{goto}{rand}{reset}",
-                color = color::Fg(c2),
-                goto = cursor::Goto(pair.0, pair.1),
-                rand = self.last_made,
-                reset = color::Fg(color::Reset),
-            )?;
+fn get_border(name: &str) -> Option<Border> {
+    match name {
+        "single" => Some(Border::Single),
+        "double" => Some(Border::Double),
+        "rounded" => Some(Border::Rounded),
+        "heavy" => Some(Border::Heavy),
+        "ascii" => Some(Border::Ascii),
+        _ => {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if !c.is_control() => Some(Border::Solid(c)),
+                _ => None,
+            }
         }
-        Ok(())
     }
+}
 
-    fn fix_last_char<T: Write>(
-        &mut self,
-        writer: &mut T,
-        c1: &dyn color::Color,
-        forbidden: &HashSet<(u16, u16)>,
-    ) -> std::io::Result<()> {
-        let pair = if !self.horizontal {
-            (self.column, self.end)
-        } else {
-            (self.end, self.column)
-        };
-        if forbidden.contains(&pair) {
-        } else {
-            write!(
-                writer,
-                "{color}{goto}{c}{reset}",
-                color = color::Fg(c1),
-                goto = cursor::Goto(pair.0, pair.1),
-                c = self.last_made,
-                reset = color::Fg(color::Reset),
-            )?;
-        }
-        Ok(())
+fn get_align(name: &str) -> Option<Align> {
+    match name {
+        "left" => Some(Align::Left),
+        "center" => Some(Align::Center),
+        "right" => Some(Align::Right),
+        _ => None,
     }
 }
 
-fn random_char() -> char {
-    const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ\
-                         abcdefghijklmnopqrstuvwxyz\
-                         0123456789)(*&^%$#@!~";
-    let idx = rand::thread_rng().gen_range(0, CHARSET.len());
-    CHARSET[idx] as char
+fn get_offset(offset: &str) -> Option<(u16, u16)> {
+    let mut parts = offset.split(',').map(|p| p.trim().parse().ok());
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(Some(x)), Some(Some(y)), None) => Some((x, y)),
+        _ => None,
+    }
 }
 
 fn get_color(color: std::ffi::OsString) -> Option<&'static dyn color::Color> {
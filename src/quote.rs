@@ -0,0 +1,63 @@
+use rand::seq::SliceRandom;
+use rmatrix::runner::CommandRunner;
+use std::path::Path;
+
+pub enum QuoteSource {
+    /// A shell command whose output is the quote, like `fortune`.
+    Command(CommandRunner),
+    /// Quotes read ahead of time, one is picked at random each time.
+    File {
+        quotes: Vec<String>,
+        picked: Option<String>,
+    },
+}
+
+impl QuoteSource {
+    /// Reads a file of quotes separated by blank lines.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let mut quotes = Vec::new();
+        let mut paragraph = String::new();
+        for line in contents.lines() {
+            if line.trim().is_empty() {
+                if !paragraph.is_empty() {
+                    quotes.push(std::mem::take(&mut paragraph));
+                }
+                continue;
+            }
+            paragraph.push_str(line);
+            paragraph.push('\n');
+        }
+        if !paragraph.is_empty() {
+            quotes.push(paragraph);
+        }
+        if quotes.is_empty() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "no quotes in file",
+            ));
+        }
+        Ok(QuoteSource::File {
+            quotes,
+            picked: None,
+        })
+    }
+
+    /// Asks for a new quote, `poll` hands it over once it's ready.
+    pub fn request(&mut self) {
+        match self {
+            QuoteSource::Command(runner) => runner.start(),
+            QuoteSource::File { quotes, picked } => {
+                *picked = quotes.choose(&mut rand::thread_rng()).cloned()
+            }
+        }
+    }
+
+    /// The requested quote, or a message saying why there isn't one.
+    pub fn poll(&mut self) -> Option<String> {
+        match self {
+            QuoteSource::Command(runner) => runner.poll().map(|r| r.unwrap_or_else(|e| e)),
+            QuoteSource::File { picked, .. } => picked.take(),
+        }
+    }
+}
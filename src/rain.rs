@@ -0,0 +1,689 @@
+//! The falling characters. Each screen column (or row, when the rain runs
+//! sideways) is a `Column` that grows a trail of random characters from one
+//! edge to the other, then starts over after a while. `Layers` stacks several
+//! rains at different depths.
+
+use crate::drawing::{Draw, Mask, Rect, Size};
+use rand::{distributions::Uniform, prelude::*};
+use std::cell::Cell;
+use std::fmt;
+use std::io::Write;
+use termion::{color, cursor, style};
+
+mod drops;
+use drops::Drops;
+
+// how long a column waits at most before falling again, in steps
+const PAUSE: u16 = 100;
+
+/// How strongly a layer of rain is drawn.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Brightness {
+    /// Faint, for rain far away.
+    Dim,
+    /// The terminal's usual weight.
+    Normal,
+    /// Bold, for rain up close.
+    Bold,
+}
+
+/// Rain filling a rect of the screen, one column per line of cells it falls
+/// along. It's also a widget, drawing everything it remembers wherever it's
+/// put. It goes on falling there from the next step.
+pub struct Rain {
+    columns: Vec<Column>,
+    // drops moving freely instead of columns, for wind and gravity
+    drops: Option<Drops>,
+    area: Rect,
+    // the corner it was last drawn at, the area moves there on the next step
+    at: Cell<(u16, u16)>,
+    reverse: bool,
+    horizontal: bool,
+    // colors of the trails and of the heads
+    trail: &'static dyn color::Color,
+    head: &'static dyn color::Color,
+    // steps per frame, and how far into the next step it's got
+    speed: f32,
+    progress: f32,
+    brightness: Brightness,
+}
+
+impl Rain {
+    /// Rain over the whole of a `screen` sized terminal, falling down unless
+    /// `horizontal`, and the other way if `reverse`.
+    pub fn new(screen: (u16, u16), reverse: bool, horizontal: bool) -> Self {
+        Self::within(Rect::new(1, 1, screen.0, screen.1), reverse, horizontal)
+    }
+
+    /// Rain that stays inside `area`, e.g. a panel next to other output.
+    pub fn within(area: Rect, reverse: bool, horizontal: bool) -> Self {
+        // the terminal counts from 1, there's nothing in row or column 0
+        let (x, y) = (area.x.max(1), area.y.max(1));
+        let width = area.width.saturating_sub(x - area.x);
+        let area = Rect::new(x, y, width, area.height.saturating_sub(y - area.y));
+        let columns = if !horizontal {
+            1..area.width
+        } else {
+            0..area.height + 1
+        }
+        .map(|c| Column::new(c, area, reverse, horizontal))
+        .collect();
+        Self {
+            columns,
+            drops: None,
+            area,
+            at: Cell::new((area.x, area.y)),
+            reverse,
+            horizontal,
+            trail: &color::Blue,
+            head: &color::White,
+            speed: 1.0,
+            progress: 0.0,
+            brightness: Brightness::Normal,
+        }
+    }
+
+    /// Steps taken per `update`, one or less so far away rain can fall slower.
+    pub fn with_speed(self, speed: f32) -> Self {
+        Self {
+            speed: speed.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// How often columns start falling again, 1 as usual and 0.5 half as often.
+    pub fn with_density(mut self, density: f32) -> Self {
+        let pause = (PAUSE as f32 / density.max(0.01)).min(u16::MAX as f32) as u16;
+        self.columns.iter_mut().for_each(|c| c.pause = pause);
+        self.drops.iter_mut().for_each(|d| d.pause = pause);
+        self
+    }
+
+    /// Dim rain looks farther away, bold rain nearer.
+    pub fn with_brightness(mut self, brightness: Brightness) -> Self {
+        self.columns
+            .iter_mut()
+            .for_each(|c| c.brightness = brightness);
+        self.drops
+            .iter_mut()
+            .for_each(|d| d.brightness = brightness);
+        Self { brightness, ..self }
+    }
+
+    /// Blows the drops sideways by `wind` cells for every cell they fall,
+    /// negative to the left (or up), with gusts coming and going around it.
+    pub fn with_wind(self, wind: f32) -> Self {
+        if wind == 0.0 {
+            return self;
+        }
+        self.with_drops(|d| d.set_wind(wind))
+    }
+
+    /// Drops start slow and speed up by `gravity` cells per step every step,
+    /// up to `terminal` cells per step if there's a limit.
+    pub fn with_gravity(self, gravity: f32, terminal: Option<f32>) -> Self {
+        if gravity <= 0.0 {
+            return self;
+        }
+        self.with_drops(|d| {
+            d.gravity = gravity;
+            d.terminal = terminal;
+        })
+    }
+
+    /// Drops throw up a few glyphs when they hit the far edge.
+    pub fn with_splash(self, splash: bool) -> Self {
+        if !splash {
+            return self;
+        }
+        self.with_drops(|d| d.splash = true)
+    }
+
+    // switches from columns to free drops, if it hasn't already, and sets them up
+    fn with_drops(mut self, set: impl FnOnce(&mut Drops)) -> Self {
+        if self.drops.is_none() {
+            let pause = self.columns.first().map_or(PAUSE, |c| c.pause);
+            let (area, reverse, horizontal) = (self.area, self.reverse, self.horizontal);
+            self.drops = Some(Drops::new(
+                area,
+                reverse,
+                horizontal,
+                pause,
+                self.brightness,
+            ));
+            self.columns = Vec::new();
+        }
+        if let Some(drops) = &mut self.drops {
+            set(drops);
+        }
+        self
+    }
+
+    /// Trails drawn in `trail` and the newest character of each in `head`.
+    pub fn with_colors(
+        self,
+        trail: &'static dyn color::Color,
+        head: &'static dyn color::Color,
+    ) -> Self {
+        Self {
+            trail,
+            head,
+            ..self
+        }
+    }
+
+    /// Moves every column on a step, drawing around what `forbidden` covers.
+    pub fn update<T: Write>(&mut self, writer: &mut T, forbidden: &Mask) -> std::io::Result<()> {
+        self.follow();
+        self.progress += self.speed;
+        while self.progress >= 1.0 {
+            self.progress -= 1.0;
+            for c in &mut self.columns {
+                c.update(writer, self.trail, self.head, forbidden)?;
+                c.changed.clear();
+            }
+            if let Some(drops) = &mut self.drops {
+                drops.update(writer, self.trail, self.head, forbidden)?;
+                drops.changed.clear();
+            }
+        }
+        Ok(())
+    }
+
+    // like `update` without drawing anything, returning the cells that changed
+    fn step(&mut self) -> Vec<(u16, u16)> {
+        let mut changed = Vec::new();
+        let nothing = Mask::new((0, 0));
+        self.follow();
+        self.progress += self.speed;
+        while self.progress >= 1.0 {
+            self.progress -= 1.0;
+            for c in &mut self.columns {
+                // writing to a sink can't fail
+                let _ = c.update(&mut std::io::sink(), self.trail, self.head, &nothing);
+                let positions = std::mem::take(&mut c.changed);
+                changed.extend(positions.into_iter().map(|p| c.cell(p)));
+            }
+            if let Some(drops) = &mut self.drops {
+                let _ = drops.update(&mut std::io::sink(), self.trail, self.head, &nothing);
+                changed.append(&mut drops.changed);
+            }
+        }
+        changed
+    }
+
+    /// Redraws whatever rain belongs at (`x`, `y`), for when a widget stops
+    /// covering it. Cells outside the rain's area are left alone.
+    pub fn restore<T: Write>(
+        &self,
+        writer: &mut T,
+        (x, y): (u16, u16),
+        forbidden: &Mask,
+    ) -> std::io::Result<()> {
+        match self.local((x, y)) {
+            Some((x0, y0)) => self.paint(writer, (x, y), self.glyph(x0, y0), forbidden),
+            None => Ok(()),
+        }
+    }
+
+    // moves the area to where the rain was last drawn, if that's somewhere else
+    fn follow(&mut self) {
+        let (x, y) = self.at.get();
+        if (x, y) == (self.area.x, self.area.y) {
+            return;
+        }
+        let area = Rect::new(x, y, self.area.width, self.area.height);
+        self.columns.iter_mut().for_each(|c| c.area = area);
+        if let Some(drops) = &mut self.drops {
+            drops.move_to(area);
+        }
+        self.area = area;
+    }
+
+    // the cell of the area shown at (`x`, `y`), going by where it was last drawn
+    fn local(&self, (x, y): (u16, u16)) -> Option<(u16, u16)> {
+        let at = self.at.get();
+        if !Rect::new(at.0, at.1, self.area.width, self.area.height).contains(x, y) {
+            return None;
+        }
+        Some((x - at.0 + self.area.x, y - at.1 + self.area.y))
+    }
+
+    // writes `glyph` at (`x`, `y`) the way this rain draws it
+    fn paint(
+        &self,
+        writer: &mut dyn Write,
+        (x, y): (u16, u16),
+        glyph: Option<(char, bool)>,
+        forbidden: &Mask,
+    ) -> std::io::Result<()> {
+        let (on, off) = shade(self.brightness, forbidden, (x, y));
+        write!(writer, "{}{}", on, cursor::Goto(x, y))?;
+        self.put(writer, glyph)?;
+        write!(writer, "{}", off)
+    }
+
+    // what's at (`x`, `y`) in the area and whether it's a head, even under widgets
+    fn glyph(&self, x: u16, y: u16) -> Option<(char, bool)> {
+        if !self.area.contains(x, y) {
+            return None;
+        }
+        if let Some(drops) = &self.drops {
+            return drops.glyph(x, y);
+        }
+        let (column, position) = if !self.horizontal {
+            (x - self.area.x, y - self.area.y + 1)
+        } else {
+            (y - self.area.y + 1, x - self.area.x + 1)
+        };
+        let column = self.columns.get(column as usize)?;
+        column
+            .glyphs
+            .get(position as usize)
+            .filter(|g| g.0 != ' ')
+            .copied()
+    }
+
+    // writes `glyph` at the cursor, a space if there's nothing there
+    fn put(&self, writer: &mut dyn Write, glyph: Option<(char, bool)>) -> std::io::Result<()> {
+        match glyph {
+            Some((c, head)) => write!(
+                writer,
+                "{color}{c}{reset}",
+                color = color::Fg(if head { self.head } else { self.trail }),
+                c = c,
+                reset = color::Fg(color::Reset),
+            ),
+            None => write!(writer, " "),
+        }
+    }
+}
+
+impl Draw for Rain {
+    fn size(&self) -> Size { Size::new(self.area.height, self.area.width) }
+    fn draw(&self, writer: &mut dyn Write, x: u16, y: u16) -> std::io::Result<Vec<Rect>> {
+        let area = self.area;
+        self.at.set((x, y));
+        for row in 0..area.height {
+            write!(writer, "{}", cursor::Goto(x, y + row))?;
+            for column in 0..area.width {
+                self.put(writer, self.glyph(area.x + column, area.y + row))?;
+            }
+        }
+        Ok(vec![Rect::new(x, y, area.width, area.height)])
+    }
+}
+
+/// Rain at several depths, listed from the farthest to the nearest. Where
+/// they overlap the nearer layer is drawn.
+pub struct Layers {
+    layers: Vec<Rain>,
+}
+
+impl Layers {
+    /// Stacks `layers`, the farthest first. They should all cover the same
+    /// area.
+    pub fn new(layers: Vec<Rain>) -> Self { Self { layers } }
+
+    /// `count` layers over `area`. The farther back a layer is the slower,
+    /// sparser and dimmer its rain.
+    pub fn depth(count: u16, area: Rect, reverse: bool, horizontal: bool) -> Self {
+        let count = count.max(1);
+        let layers = (1..=count)
+            .map(|i| {
+                let near = i as f32 / count as f32;
+                let brightness = if count == 1 {
+                    Brightness::Normal
+                } else if i == count {
+                    Brightness::Bold
+                } else if near <= 0.5 {
+                    Brightness::Dim
+                } else {
+                    Brightness::Normal
+                };
+                Rain::within(area, reverse, horizontal)
+                    .with_speed(near)
+                    .with_density(0.5 + near / 2.0)
+                    .with_brightness(brightness)
+            })
+            .collect();
+        Self { layers }
+    }
+
+    /// See `Rain::with_wind`, for every layer.
+    pub fn with_wind(self, wind: f32) -> Self {
+        Self {
+            layers: self.layers.into_iter().map(|l| l.with_wind(wind)).collect(),
+        }
+    }
+
+    /// See `Rain::with_gravity`, for every layer.
+    pub fn with_gravity(self, gravity: f32, terminal: Option<f32>) -> Self {
+        Self {
+            layers: self
+                .layers
+                .into_iter()
+                .map(|l| l.with_gravity(gravity, terminal))
+                .collect(),
+        }
+    }
+
+    /// See `Rain::with_splash`, for every layer.
+    pub fn with_splash(self, splash: bool) -> Self {
+        Self {
+            layers: self
+                .layers
+                .into_iter()
+                .map(|l| l.with_splash(splash))
+                .collect(),
+        }
+    }
+
+    /// See `Rain::with_colors`, for every layer.
+    pub fn with_colors(
+        self,
+        trail: &'static dyn color::Color,
+        head: &'static dyn color::Color,
+    ) -> Self {
+        Self {
+            layers: self
+                .layers
+                .into_iter()
+                .map(|l| l.with_colors(trail, head))
+                .collect(),
+        }
+    }
+
+    /// Moves every layer on without drawing anything, returning the cells that
+    /// changed for `restore` to draw.
+    pub fn step(&mut self) -> Vec<(u16, u16)> {
+        self.layers.iter_mut().flat_map(Rain::step).collect()
+    }
+
+    /// See `Rain::restore`, the nearest glyph at (`x`, `y`) wins.
+    pub fn restore<T: Write>(
+        &self,
+        writer: &mut T,
+        (x, y): (u16, u16),
+        forbidden: &Mask,
+    ) -> std::io::Result<()> {
+        let (layer, glyph) = self.top(x, y);
+        match layer {
+            Some(layer) if layer.local((x, y)).is_some() => {
+                layer.paint(writer, (x, y), glyph, forbidden)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // the nearest layer with something shown at (`x`, `y`), or the nearest at all
+    fn top(&self, x: u16, y: u16) -> (Option<&Rain>, Option<(char, bool)>) {
+        let found = (self.layers.iter().rev()).find_map(|l| {
+            let (x0, y0) = l.local((x, y))?;
+            Some((l, l.glyph(x0, y0)?))
+        });
+        match found {
+            Some((layer, glyph)) => (Some(layer), Some(glyph)),
+            None => (self.layers.last(), None),
+        }
+    }
+}
+
+impl Draw for Layers {
+    fn size(&self) -> Size { self.layers.last().map_or(Size::new(0, 0), |l| l.size()) }
+    fn draw(&self, writer: &mut dyn Write, x: u16, y: u16) -> std::io::Result<Vec<Rect>> {
+        let area = match self.layers.last() {
+            Some(layer) => Rect::new(x, y, layer.area.width, layer.area.height),
+            None => return Ok(Vec::new()),
+        };
+        self.layers.iter().for_each(|l| l.at.set((x, y)));
+        let nothing = Mask::new((0, 0));
+        for cell in area.cells() {
+            let (layer, glyph) = self.top(cell.0, cell.1);
+            if let Some(layer) = layer {
+                layer.paint(writer, cell, glyph, &nothing)?;
+            }
+        }
+        Ok(vec![area])
+    }
+}
+
+/// One line of falling characters.
+pub struct Column {
+    start: u16,
+    end: u16,
+    max_height: u16,
+    column: u16,
+    // where the rain is, `column` and positions count from its corner
+    area: Rect,
+    last_made: char,
+    delay: u16,
+    reverse: bool,
+    horizontal: bool,
+    // what's at every position, even under widgets, and whether it's the head
+    glyphs: Vec<(char, bool)>,
+    // positions whose glyphs changed since they were last looked at
+    changed: Vec<u16>,
+    // longest wait before falling again
+    pause: u16,
+    brightness: Brightness,
+}
+
+impl Column {
+    /// The rain in column `column` of `area` (or row, if `horizontal`),
+    /// falling backwards if `rev`. Starts after a random delay.
+    pub fn new(column: u16, area: Rect, rev: bool, horizontal: bool) -> Self {
+        let max_height = if !horizontal { area.height } else { area.width };
+        Self {
+            start: if !rev { horizontal as u16 } else { max_height },
+            end: if !rev { horizontal as u16 } else { max_height },
+            max_height,
+            column,
+            area,
+            last_made: ' ',
+            delay: Uniform::new_inclusive(0, if !horizontal { 150 } else { 300 })
+                .sample(&mut rand::thread_rng()),
+            reverse: rev,
+            horizontal,
+            glyphs: vec![(' ', false); max_height as usize + 2],
+            changed: Vec::new(),
+            pause: PAUSE,
+            brightness: Brightness::Normal,
+        }
+    }
+
+    // the screen cell at `position`
+    fn cell(&self, position: u16) -> (u16, u16) {
+        let (x, y) = if !self.horizontal {
+            (self.column, position)
+        } else {
+            (position, self.column)
+        };
+        (self.area.x - 1 + x, self.area.y - 1 + y)
+    }
+
+    // under a widget, or off the edge of the rain's area
+    fn hidden(&self, (x, y): (u16, u16), forbidden: &Mask) -> bool {
+        forbidden.contains(x, y) || !self.area.contains(x, y)
+    }
+
+    fn set_glyph(&mut self, position: u16, glyph: (char, bool)) {
+        if let Some(g) = self.glyphs.get_mut(position as usize) {
+            *g = glyph;
+            self.changed.push(position);
+        }
+    }
+    /// Grows, shrinks or waits for one step.
+    pub fn update<T: Write>(
+        &mut self,
+        writer: &mut T,
+        c1: &dyn color::Color,
+        c2: &dyn color::Color,
+        forbidden: &Mask,
+    ) -> std::io::Result<()> {
+        if self.delay == 0 {
+            let action = Uniform::new_inclusive(0, 2).sample(&mut rand::thread_rng());
+            let lowest = if !self.horizontal { 0 } else { 1 };
+            if (!self.reverse && self.max_height != self.end && self.max_height != self.start)
+                || (self.reverse && lowest != self.end && lowest != self.start)
+            {
+                if action == 1 || action == 2 {
+                    self.add_last_char(writer, c1, c2, forbidden)?;
+                }
+                if action == 2 || action == 3 {
+                    self.delete_first_char(writer, forbidden)?;
+                }
+            } else if ((!self.reverse && self.max_height == self.end)
+                || (self.reverse && lowest == self.end))
+                && self.end == self.start
+            {
+                // finished column
+                let new = if !self.reverse {
+                    lowest
+                } else {
+                    self.max_height
+                };
+                self.start = new;
+                self.end = new;
+                self.delay = Uniform::new_inclusive(0, self.pause).sample(&mut rand::thread_rng());
+            } else if (!self.reverse && self.max_height == self.end)
+                || (self.reverse && lowest == self.end)
+            {
+                // finishing up column
+                self.delete_first_char(writer, forbidden)?;
+                self.fix_last_char(writer, c1, forbidden)?;
+            }
+        } else {
+            self.delay -= 1;
+            self.delete_last_char(writer, forbidden)?;
+        }
+
+        Ok(())
+    }
+
+    fn delete_first_char<T: Write>(
+        &mut self,
+        writer: &mut T,
+        forbidden: &Mask,
+    ) -> std::io::Result<()> {
+        // delete last char created
+        let pair = self.cell(self.start);
+        self.set_glyph(self.start, (' ', false));
+        if self.hidden(pair, forbidden) {
+        } else {
+            write!(writer, "{goto} ", goto = cursor::Goto(pair.0, pair.1))?;
+        }
+        if !self.reverse {
+            self.start += 1;
+        } else {
+            self.start -= 1;
+        };
+        Ok(())
+    }
+
+    fn delete_last_char<T: Write>(
+        &mut self,
+        writer: &mut T,
+        forbidden: &Mask,
+    ) -> std::io::Result<()> {
+        let lowest = if self.horizontal { 1 } else { 0 };
+        let position = if !self.reverse {
+            self.max_height
+        } else {
+            lowest
+        };
+        let pair = self.cell(position);
+        self.set_glyph(position, (' ', false));
+        if self.hidden(pair, forbidden) {
+        } else {
+            write!(writer, "{goto} ", goto = cursor::Goto(pair.0, pair.1))?;
+        }
+        Ok(())
+    }
+
+    fn add_last_char<T: Write>(
+        &mut self,
+        writer: &mut T,
+        c1: &dyn color::Color,
+        c2: &dyn color::Color,
+        forbidden: &Mask,
+    ) -> std::io::Result<()> {
+        // fix color of old char
+        self.fix_last_char(writer, c1, forbidden)?;
+        if !self.reverse {
+            self.end += 1;
+        } else {
+            self.end -= 1;
+        };
+        let pair = self.cell(self.end);
+        // create new char at end, remembered even if it can't be seen
+        self.last_made = random_char();
+        self.set_glyph(self.end, (self.last_made, true));
+        if self.hidden(pair, forbidden) {
+        } else {
+            let (dim, undim) = shade(self.brightness, forbidden, pair);
+            write!(
+                writer,
+                "{dim}{color}{goto}{rand}{reset}{undim}",
+                dim = dim,
+                color = color::Fg(c2),
+                goto = cursor::Goto(pair.0, pair.1),
+                rand = self.last_made,
+                reset = color::Fg(color::Reset),
+                undim = undim,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn fix_last_char<T: Write>(
+        &mut self,
+        writer: &mut T,
+        c1: &dyn color::Color,
+        forbidden: &Mask,
+    ) -> std::io::Result<()> {
+        let pair = self.cell(self.end);
+        self.set_glyph(self.end, (self.last_made, false));
+        if self.hidden(pair, forbidden) {
+        } else {
+            let (dim, undim) = shade(self.brightness, forbidden, pair);
+            write!(
+                writer,
+                "{dim}{color}{goto}{c}{reset}{undim}",
+                dim = dim,
+                color = color::Fg(c1),
+                goto = cursor::Goto(pair.0, pair.1),
+                c = self.last_made,
+                reset = color::Fg(color::Reset),
+                undim = undim,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// Escape sequences around a glyph drawn at `pair`. Anything inside a
+// see-through widget is always faint, so the widget stands out.
+pub(crate) fn shade(
+    brightness: Brightness,
+    forbidden: &Mask,
+    pair: (u16, u16),
+) -> (&'static dyn fmt::Display, &'static dyn fmt::Display) {
+    // `style::NoBold` is double underline on some terminals
+    if forbidden.dims(pair.0, pair.1) {
+        return (&style::Faint, &style::NoFaint);
+    }
+    match brightness {
+        Brightness::Dim => (&style::Faint, &style::NoFaint),
+        Brightness::Normal => (&"", &""),
+        Brightness::Bold => (&style::Bold, &style::NoFaint),
+    }
+}
+
+fn random_char() -> char {
+    const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ\
+                         abcdefghijklmnopqrstuvwxyz\
+                         0123456789)(*&^%$#@!~";
+    let idx = rand::thread_rng().gen_range(0, CHARSET.len());
+    CHARSET[idx] as char
+}
@@ -0,0 +1,319 @@
+// Rain as drops moving freely, for wind and gravity. Drops aren't tied to a
+// column here, each one moves on its own slant and speed and leaves a trail of
+// the cells it passed through.
+use super::{random_char, shade, Brightness};
+use crate::drawing::{Mask, Rect};
+use rand::prelude::*;
+use std::collections::VecDeque;
+use std::io::Write;
+use termion::{color, cursor};
+
+// how far gusts swing the wind either way, at least
+const GUST: f32 = 0.5;
+// how quickly the wind turns towards the next gust, per step
+const TURN: f32 = 0.005;
+// speed of a drop when it starts falling under gravity
+const START_SPEED: f32 = 0.1;
+
+pub(super) struct Drops {
+    area: Rect,
+    reverse: bool,
+    horizontal: bool,
+    // cells across per cell fallen, on average and right now
+    wind: f32,
+    now: f32,
+    target: f32,
+    // speed gained every step, and the most there is
+    pub(super) gravity: f32,
+    pub(super) terminal: Option<f32>,
+    pub(super) splash: bool,
+    drops: Vec<Raindrop>,
+    // scattered where drops hit the far edge, with the steps they have left
+    splashes: Vec<((u16, u16), char, u16)>,
+    // what's in every cell of the area, and whether it's a head
+    glyphs: Vec<Option<(char, bool)>>,
+    // cells whose glyphs changed since they were last looked at
+    pub(super) changed: Vec<(u16, u16)>,
+    pub(super) pause: u16,
+    pub(super) brightness: Brightness,
+}
+
+struct Raindrop {
+    // cells from the edge the rain starts at, and across it
+    along: f32,
+    across: f32,
+    speed: f32,
+    drift: f32,
+    length: usize,
+    // oldest first, with what was written there
+    trail: VecDeque<((u16, u16), char)>,
+}
+
+impl Drops {
+    pub(super) fn new(
+        area: Rect,
+        reverse: bool,
+        horizontal: bool,
+        pause: u16,
+        brightness: Brightness,
+    ) -> Self {
+        Self {
+            area,
+            reverse,
+            horizontal,
+            wind: 0.0,
+            now: 0.0,
+            target: 0.0,
+            gravity: 0.0,
+            terminal: None,
+            splash: false,
+            drops: Vec::new(),
+            splashes: Vec::new(),
+            glyphs: vec![None; area.width as usize * area.height as usize],
+            changed: Vec::new(),
+            pause,
+            brightness,
+        }
+    }
+
+    pub(super) fn set_wind(&mut self, wind: f32) {
+        self.wind = wind;
+        self.now = wind;
+        self.target = wind;
+    }
+
+    // takes everything along to the same spot in `area`, the same size
+    pub(super) fn move_to(&mut self, area: Rect) {
+        let from = self.area;
+        let shift = |(x, y): (u16, u16)| (x - from.x + area.x, y - from.y + area.y);
+        for drop in &mut self.drops {
+            drop.trail.iter_mut().for_each(|t| t.0 = shift(t.0));
+        }
+        self.splashes.iter_mut().for_each(|s| s.0 = shift(s.0));
+        self.changed.iter_mut().for_each(|c| *c = shift(*c));
+        self.area = area;
+    }
+
+    // (length along the fall, width across it)
+    fn extent(&self) -> (u16, u16) {
+        if !self.horizontal {
+            (self.area.height, self.area.width)
+        } else {
+            (self.area.width, self.area.height)
+        }
+    }
+
+    // the screen cell a drop at (`along`, `across`) is in, if it's in the area
+    fn cell(&self, along: f32, across: f32) -> Option<(u16, u16)> {
+        let (length, width) = self.extent();
+        let (along, across) = (along.floor(), across.round());
+        if along < 0.0 || across < 0.0 || along >= length as f32 || across >= width as f32 {
+            return None;
+        }
+        let along = if !self.reverse {
+            along as u16
+        } else {
+            length - 1 - along as u16
+        };
+        Some(if !self.horizontal {
+            (self.area.x + across as u16, self.area.y + along)
+        } else {
+            (self.area.x + along, self.area.y + across as u16)
+        })
+    }
+
+    fn index(&self, (x, y): (u16, u16)) -> Option<usize> {
+        if !self.area.contains(x, y) {
+            return None;
+        }
+        Some((y - self.area.y) as usize * self.area.width as usize + (x - self.area.x) as usize)
+    }
+
+    pub(super) fn glyph(&self, x: u16, y: u16) -> Option<(char, bool)> {
+        self.index((x, y)).and_then(|i| self.glyphs[i])
+    }
+
+    pub(super) fn update<T: Write>(
+        &mut self,
+        writer: &mut T,
+        c1: &dyn color::Color,
+        c2: &dyn color::Color,
+        forbidden: &Mask,
+    ) -> std::io::Result<()> {
+        let mut rng = rand::thread_rng();
+        // the wind swings slowly from one gust to the next
+        if self.wind != 0.0 {
+            if (self.now - self.target).abs() <= TURN {
+                let swing = GUST.max(self.wind.abs() / 2.0);
+                self.target = self.wind + rng.gen_range(-swing, swing);
+            }
+            self.now += (self.target - self.now).clamp(-TURN, TURN);
+        }
+
+        let (length, width) = self.extent();
+        // about as many new drops as columns that would start falling
+        let mut due = width as f32 / (self.pause as f32 / 2.0 + length as f32);
+        // upwind of the area too, so drops blow in from the side
+        let reach = -self.now * length as f32;
+        let (low, high) = (reach.min(0.0), width as f32 + reach.max(0.0));
+        due *= (high - low) / width.max(1) as f32;
+        while due > 0.0 {
+            if rng.gen::<f32>() < due {
+                self.drops.push(Raindrop {
+                    along: 0.0,
+                    across: rng.gen_range(low, high),
+                    speed: if self.gravity > 0.0 {
+                        START_SPEED
+                    } else {
+                        rng.gen_range(0.5, 1.0)
+                    },
+                    drift: self.now,
+                    length: rng.gen_range(4, length.max(5) as usize),
+                    trail: VecDeque::new(),
+                });
+            }
+            due -= 1.0;
+        }
+
+        let mut drops = std::mem::take(&mut self.drops);
+        for drop in &mut drops {
+            if drop.along < length as f32 {
+                // a cell at a time, so fast drops don't leave gaps
+                let steps = drop.speed.ceil().max(1.0);
+                for _ in 0..steps as u16 {
+                    drop.along += drop.speed / steps;
+                    drop.across += drop.drift * drop.speed / steps;
+                    self.enter(writer, drop, c1, c2, forbidden)?;
+                }
+                drop.speed += self.gravity;
+                if let Some(terminal) = self.terminal {
+                    drop.speed = drop.speed.min(terminal);
+                }
+                if drop.along >= length as f32 && self.splash {
+                    self.scatter(writer, drop, c1, forbidden)?;
+                }
+            }
+            let gone = drop.along >= length as f32;
+            // the tail keeps up with the head, even after the head's gone
+            let behind = if gone {
+                drop.speed.ceil().max(1.0) as usize
+            } else {
+                drop.trail.len().saturating_sub(drop.length)
+            };
+            for _ in 0..behind {
+                if let Some((cell, c)) = drop.trail.pop_front() {
+                    if self.holds(cell, c) {
+                        self.set(writer, cell, None, c1, forbidden)?;
+                    }
+                }
+            }
+        }
+        drops.retain(|d| !d.trail.is_empty() || d.along < length as f32);
+        self.drops = drops;
+
+        let mut splashes = std::mem::take(&mut self.splashes);
+        for splash in &mut splashes {
+            splash.2 -= 1;
+            if splash.2 == 0 && self.holds(splash.0, splash.1) {
+                self.set(writer, splash.0, None, c1, forbidden)?;
+            }
+        }
+        splashes.retain(|s| s.2 > 0);
+        self.splashes = splashes;
+        Ok(())
+    }
+
+    // moves the head of `drop` into the cell it's got to, if that's a new one
+    fn enter<T: Write>(
+        &mut self,
+        writer: &mut T,
+        drop: &mut Raindrop,
+        c1: &dyn color::Color,
+        c2: &dyn color::Color,
+        forbidden: &Mask,
+    ) -> std::io::Result<()> {
+        let was = drop.trail.back().copied();
+        let cell = match self.cell(drop.along, drop.across) {
+            Some(cell) if Some(cell) != was.map(|t| t.0) => cell,
+            _ => return Ok(()),
+        };
+        // the old head turns into trail
+        if let Some((old, c)) = was {
+            if self.holds(old, c) {
+                self.set(writer, old, Some((c, false)), c1, forbidden)?;
+            }
+        }
+        let c = random_char();
+        self.set(writer, cell, Some((c, true)), c2, forbidden)?;
+        drop.trail.push_back((cell, c));
+        Ok(())
+    }
+
+    // a few glyphs thrown up around where `drop` hit the far edge
+    fn scatter<T: Write>(
+        &mut self,
+        writer: &mut T,
+        drop: &Raindrop,
+        c1: &dyn color::Color,
+        forbidden: &Mask,
+    ) -> std::io::Result<()> {
+        let mut rng = rand::thread_rng();
+        let bottom = self.extent().0 as f32 - 1.0;
+        for _ in 0..rng.gen_range(2, 5) {
+            let along = bottom - rng.gen_range(0, 3) as f32;
+            let side = if rng.gen() { 1.0 } else { -1.0 };
+            let across = drop.across + side * rng.gen_range(1, 4) as f32;
+            if let Some(cell) = self.cell(along, across) {
+                // drops falling through it don't count
+                if self.glyph(cell.0, cell.1).is_some() {
+                    continue;
+                }
+                let c = random_char();
+                self.set(writer, cell, Some((c, false)), c1, forbidden)?;
+                self.splashes.push((cell, c, rng.gen_range(3, 9)));
+            }
+        }
+        Ok(())
+    }
+
+    // whether `c` is still at `cell`, another drop may have written over it since
+    fn holds(&self, cell: (u16, u16), c: char) -> bool {
+        matches!(self.glyph(cell.0, cell.1), Some((g, _)) if g == c)
+    }
+
+    // remembers `glyph` at `cell` and draws it unless a widget is in the way
+    fn set<T: Write>(
+        &mut self,
+        writer: &mut T,
+        cell: (u16, u16),
+        glyph: Option<(char, bool)>,
+        color: &dyn color::Color,
+        forbidden: &Mask,
+    ) -> std::io::Result<()> {
+        let i = match self.index(cell) {
+            Some(i) => i,
+            None => return Ok(()),
+        };
+        self.glyphs[i] = glyph;
+        self.changed.push(cell);
+        if forbidden.contains(cell.0, cell.1) {
+            return Ok(());
+        }
+        match glyph {
+            Some((c, _)) => {
+                let (dim, undim) = shade(self.brightness, forbidden, cell);
+                write!(
+                    writer,
+                    "{dim}{color}{goto}{c}{reset}{undim}",
+                    dim = dim,
+                    color = color::Fg(color),
+                    goto = cursor::Goto(cell.0, cell.1),
+                    c = c,
+                    reset = color::Fg(color::Reset),
+                    undim = undim,
+                )
+            }
+            None => write!(writer, "{} ", cursor::Goto(cell.0, cell.1)),
+        }
+    }
+}
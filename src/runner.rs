@@ -0,0 +1,139 @@
+//! Shell commands run without holding up the animation, for quotes and
+//! anything else that comes from another program.
+
+use std::io::Read;
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Runs a shell command on a background thread, so a slow command can't
+/// stall the animation. Output comes back through `poll`.
+pub struct CommandRunner {
+    cmd: String,
+    timeout: Duration,
+    pending: Option<Receiver<Result<String, String>>>,
+}
+
+impl CommandRunner {
+    /// `cmd` run with `sh -c`, killed if it takes longer than `timeout`.
+    pub fn new(cmd: &str, timeout: Duration) -> Self {
+        Self {
+            cmd: cmd.to_owned(),
+            timeout,
+            pending: None,
+        }
+    }
+
+    /// Starts the command, unless the last run hasn't finished yet.
+    pub fn start(&mut self) {
+        if self.pending.is_some() {
+            return;
+        }
+        let (tx, rx) = channel();
+        let cmd = self.cmd.clone();
+        let timeout = self.timeout;
+        thread::spawn(move || {
+            // nobody is listening anymore if this fails
+            let _ = tx.send(run(&cmd, timeout));
+        });
+        self.pending = Some(rx);
+    }
+
+    /// The output of a finished run, or a message saying why it failed.
+    pub fn poll(&mut self) -> Option<Result<String, String>> {
+        let result = match self.pending.as_ref()?.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => Err(format!("`{}` crashed", self.cmd)),
+        };
+        self.pending = None;
+        Some(result)
+    }
+}
+
+fn run(cmd: &str, timeout: Duration) -> Result<String, String> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(cmd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        // its own process group, so whatever it starts can be stopped with it
+        .process_group(0)
+        .spawn()
+        .map_err(|e| format!("Couldn't run `{}`: {}", cmd, e))?;
+    // read on their own threads so a chatty command can't fill the pipe and block
+    let stdout = read_all(child.stdout.take());
+    let stderr = read_all(child.stderr.take());
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => thread::sleep(Duration::from_millis(10)),
+            Ok(None) => {
+                kill(&mut child);
+                return Err(format!("`{}` took longer than {:?}", cmd, timeout));
+            }
+            Err(e) => return Err(format!("Couldn't run `{}`: {}", cmd, e)),
+        }
+    };
+    let out = match finish(&stdout, deadline, &mut child) {
+        Some(out) => out,
+        None => return Err(format!("`{}` took longer than {:?}", cmd, timeout)),
+    };
+    if status.success() {
+        if out.trim().is_empty() {
+            return Err(format!("`{}` printed nothing", cmd));
+        }
+        return Ok(out);
+    }
+    // what `sh` exits with when it can't find the program
+    if status.code() == Some(127) {
+        return Err(format!(
+            "Couldn't find `{}`, is it installed?",
+            cmd.split_whitespace().next().unwrap_or(cmd)
+        ));
+    }
+    let err = finish(&stderr, deadline, &mut child).unwrap_or_default();
+    match err.lines().find(|l| !l.trim().is_empty()) {
+        Some(line) => Err(format!("`{}` failed:\n{}", cmd, line)),
+        None => Err(format!("`{}` failed ({})", cmd, status)),
+    }
+}
+
+// The rest of what was read from a pipe once the command is done. Whatever it
+// left running in the background may hold the pipe open, that gets killed if
+// it's still there at the `deadline`.
+fn finish(pipe: &Receiver<String>, deadline: Instant, child: &mut Child) -> Option<String> {
+    let left = deadline.saturating_duration_since(Instant::now());
+    match pipe.recv_timeout(left) {
+        Ok(text) => Some(text),
+        // something in the group is still alive, so the group id can't have been reused
+        Err(_) => {
+            kill(child);
+            None
+        }
+    }
+}
+
+// stops `child` and everything it started, which closes the pipes they share
+fn kill(child: &mut Child) {
+    unsafe {
+        libc::killpg(child.id() as libc::pid_t, libc::SIGKILL);
+    }
+    let _ = child.wait();
+}
+
+fn read_all<R: Read + Send + 'static>(pipe: Option<R>) -> Receiver<String> {
+    let (tx, rx) = channel();
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        let _ = tx.send(String::from_utf8_lossy(&bytes).to_string());
+    });
+    rx
+}
@@ -0,0 +1,176 @@
+//! Widgets drawn over an `Effect`, a frame at a time, the way `rmatrix` puts
+//! its clock and quotes over the rain.
+
+use crate::drawing::{Anchor, Cached, Mask, Placer, Rect};
+use crate::effect::Effect;
+use std::io::Write;
+
+/// Keeps track of what the widgets cover, so the effect can stay out from
+/// under them. Every frame the widgets are drawn with `draw`, most important
+/// first, then `finish` hands the cells they let go of back to the effect.
+pub struct Screen {
+    size: (u16, u16),
+    see_through: bool,
+    placer: Placer,
+    drawn: Overlay,
+    // what widgets covered last frame, and how the effect was let through
+    covered: Vec<Rect>,
+    mask: Mask,
+}
+
+// What the widgets take up this frame.
+#[derive(Default)]
+struct Overlay {
+    area: Vec<Rect>,
+    glyphs: Vec<Rect>,
+    // drawn this frame, wiping out whatever was in their empty cells
+    redrawn: Vec<Rect>,
+}
+
+impl Screen {
+    /// Nothing drawn yet on a `size` terminal. With `see_through` the effect
+    /// shows through the empty cells of widgets, dimmed.
+    pub fn new(size: (u16, u16), see_through: bool) -> Self {
+        Self {
+            size,
+            see_through,
+            placer: Placer::new(size),
+            drawn: Overlay::default(),
+            covered: Vec::new(),
+            mask: Mask::new(size),
+        }
+    }
+
+    /// Draws `widget` where `anchor` and `margin` put it, out of the way of
+    /// the widgets drawn before it this frame. Hidden if there's no room.
+    pub fn draw(
+        &mut self,
+        writer: &mut dyn Write,
+        widget: &mut Cached,
+        anchor: Anchor,
+        margin: (u16, u16),
+    ) -> std::io::Result<()> {
+        let placer = &mut self.placer;
+        let at = widget
+            .size()
+            .and_then(|size| placer.place(size, anchor, margin));
+        if widget.draw(writer, at)? {
+            self.drawn.redrawn.extend(widget.area());
+        }
+        self.drawn.area.extend(widget.area());
+        self.drawn.glyphs.extend(widget.glyphs());
+        Ok(())
+    }
+
+    /// Ends the frame. Works out what the widgets cover now and has `effect`
+    /// redraw the cells they uncovered or wiped out. The next `draw` starts a
+    /// new frame.
+    pub fn finish(&mut self, writer: &mut dyn Write, effect: &dyn Effect) -> std::io::Result<()> {
+        let drawn = std::mem::take(&mut self.drawn);
+        let mut forbidden = Mask::new(self.size);
+        if self.see_through {
+            drawn.glyphs.iter().for_each(|rect| forbidden.cover(rect));
+            drawn.area.iter().for_each(|rect| forbidden.shade(rect));
+        } else {
+            drawn.area.iter().for_each(|rect| forbidden.cover(rect));
+        }
+        // widgets that moved or shrank uncover the rain that kept falling behind
+        // them, and the ones drawn again painted over what showed through them
+        let last = &self.mask;
+        let uncovered = (self.covered.iter().flat_map(Rect::cells))
+            .filter(|&(x, y)| !forbidden.contains(x, y) && !forbidden.same_at(last, x, y));
+        let wiped =
+            (drawn.redrawn.iter().flat_map(Rect::cells)).filter(|&(x, y)| forbidden.dims(x, y));
+        for cell in uncovered.chain(wiped) {
+            effect.restore(writer, cell, &forbidden)?;
+        }
+        self.covered = drawn.area;
+        self.mask = forbidden;
+        self.placer = Placer::new(self.size);
+        Ok(())
+    }
+
+    /// What the widgets covered when the last frame was finished, for the
+    /// effect to stay out of.
+    pub fn mask(&self) -> &Mask { &self.mask }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawing::{Draw, Label};
+    use std::cell::RefCell;
+
+    // remembers which cells it was asked to restore
+    #[derive(Default)]
+    struct Restored(RefCell<Vec<(u16, u16)>>);
+
+    impl Effect for Restored {
+        fn init(&mut self, _area: Rect) {}
+        fn step(&mut self) {}
+        fn render(&mut self, _writer: &mut dyn Write, _forbidden: &Mask) -> std::io::Result<()> {
+            Ok(())
+        }
+        fn restore(
+            &self,
+            _writer: &mut dyn Write,
+            cell: (u16, u16),
+            _forbidden: &Mask,
+        ) -> std::io::Result<()> {
+            self.0.borrow_mut().push(cell);
+            Ok(())
+        }
+    }
+
+    fn label(text: &'static str) -> Cached {
+        let mut widget = Cached::new();
+        widget.update(text, || Box::new(Label::from(text)) as Box<dyn Draw>);
+        widget
+    }
+
+    #[test]
+    fn keeps_the_effect_out_from_under_widgets() {
+        let (mut screen, effect) = (Screen::new((20, 5), false), Restored::default());
+        let mut out = Vec::new();
+        let mut widget = label("hi");
+        screen
+            .draw(&mut out, &mut widget, Anchor::TopLeft, (0, 0))
+            .unwrap();
+        screen.finish(&mut out, &effect).unwrap();
+        assert!(screen.mask().contains(1, 1) && screen.mask().contains(2, 1));
+        assert!(!screen.mask().contains(3, 1));
+        assert!(effect.0.borrow().is_empty());
+    }
+
+    #[test]
+    fn gives_back_what_widgets_let_go_of() {
+        let (mut screen, effect) = (Screen::new((20, 5), false), Restored::default());
+        let mut out = Vec::new();
+        let mut widget = label("hi");
+        screen
+            .draw(&mut out, &mut widget, Anchor::TopLeft, (0, 0))
+            .unwrap();
+        screen.finish(&mut out, &effect).unwrap();
+        screen
+            .draw(&mut out, &mut widget, Anchor::TopLeft, (1, 0))
+            .unwrap();
+        screen.finish(&mut out, &effect).unwrap();
+        // the cell it moved off of, the one it's still on stays covered
+        assert_eq!(*effect.0.borrow(), vec![(1, 1)]);
+        assert!(screen.mask().contains(2, 1) && screen.mask().contains(3, 1));
+    }
+
+    #[test]
+    fn redrawn_widgets_wipe_what_showed_through() {
+        let (mut screen, effect) = (Screen::new((20, 5), true), Restored::default());
+        let mut out = Vec::new();
+        let mut widget = label("a b");
+        screen
+            .draw(&mut out, &mut widget, Anchor::TopLeft, (0, 0))
+            .unwrap();
+        screen.finish(&mut out, &effect).unwrap();
+        // the gap lets the effect through, and was just painted over
+        assert!(screen.mask().dims(2, 1));
+        assert_eq!(*effect.0.borrow(), vec![(2, 1)]);
+    }
+}
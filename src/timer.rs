@@ -0,0 +1,225 @@
+use chrono::{DateTime, Local, NaiveTime};
+use std::time::{Duration, Instant};
+
+pub enum Mode {
+    Countdown(Duration),
+    Until(NaiveTime),
+    Stopwatch,
+    Pomodoro { work: Duration, rest: Duration },
+}
+
+pub struct Timer {
+    mode: Mode,
+    // time counted before the last (re)start
+    banked: Duration,
+    started: Option<Instant>,
+    resting: bool,
+    finished: bool,
+    deadline: Option<DateTime<Local>>,
+}
+
+impl Timer {
+    pub fn from(mode: Mode) -> Self {
+        let deadline = match mode {
+            Mode::Until(end) => {
+                let now = Local::now();
+                let left = end.signed_duration_since(now.time());
+                // already past today, so it's tomorrow
+                let left = if left < chrono::Duration::zero() {
+                    left + chrono::Duration::days(1)
+                } else {
+                    left
+                };
+                Some(now + left)
+            }
+            _ => None,
+        };
+        Self {
+            mode,
+            deadline,
+            banked: Duration::from_secs(0),
+            started: Some(Instant::now()),
+            resting: false,
+            finished: false,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        // a time of day comes when it comes
+        if let Mode::Until(_) = self.mode {
+            return;
+        }
+        match self.started.take() {
+            Some(s) => self.banked += s.elapsed(),
+            None => self.started = Some(Instant::now()),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        if let Mode::Until(_) = self.mode {
+            return;
+        }
+        self.banked = Duration::from_secs(0);
+        self.started = None;
+        self.resting = false;
+        self.finished = false;
+    }
+
+    /// Advances pomodoro phases. True when a countdown or phase just ran out.
+    pub fn tick(&mut self) -> bool {
+        let remaining = match self.remaining() {
+            Some(r) => r,
+            None => return false,
+        };
+        if remaining > Duration::from_secs(0) {
+            self.finished = false;
+            return false;
+        }
+        if let Mode::Pomodoro { .. } = self.mode {
+            self.resting = !self.resting;
+            self.banked = Duration::from_secs(0);
+            self.started = Some(Instant::now());
+            return true;
+        }
+        let ended = !self.finished;
+        self.finished = true;
+        ended
+    }
+
+    /// What the big digits should show.
+    pub fn display(&self) -> String {
+        let secs = match self.remaining() {
+            // round up so "00:00" only shows once the time is actually up
+            Some(r) => r.as_secs() + (r.subsec_nanos() > 0) as u64,
+            None => self.elapsed().as_secs(),
+        };
+        if secs >= 3600 {
+            format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+        } else {
+            format!("{:02}:{:02}", secs / 60, secs % 60)
+        }
+    }
+
+    /// A line to show under the digits, if the state isn't obvious.
+    pub fn caption(&self) -> Option<&'static str> {
+        match self.mode {
+            // wall clock time, it can't be paused
+            Mode::Until(_) => None,
+            _ if self.started.is_none() => Some("paused"),
+            Mode::Pomodoro { .. } if self.resting => Some("break"),
+            Mode::Pomodoro { .. } => Some("work"),
+            _ => None,
+        }
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.banked + self.started.map_or(Duration::from_secs(0), |s| s.elapsed())
+    }
+
+    // `None` for timers that count up.
+    fn remaining(&self) -> Option<Duration> {
+        let total = match self.mode {
+            Mode::Countdown(d) => d,
+            Mode::Pomodoro { rest, .. } if self.resting => rest,
+            Mode::Pomodoro { work, .. } => work,
+            Mode::Until(_) => {
+                let left = self.deadline?.signed_duration_since(Local::now());
+                return Some(left.to_std().unwrap_or_else(|_| Duration::from_secs(0)));
+            }
+            Mode::Stopwatch => return None,
+        };
+        Some(
+            total
+                .checked_sub(self.elapsed())
+                .unwrap_or_else(|| Duration::from_secs(0)),
+        )
+    }
+}
+
+/// Reads durations like "25m", "90s", "1h30m". A bare number is minutes.
+/// Zero and durations too long to count are refused.
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    if let Ok(minutes) = text.parse::<u64>() {
+        if minutes == 0 {
+            return None;
+        }
+        return Some(Duration::from_secs(minutes.checked_mul(60)?));
+    }
+    let mut secs: u64 = 0;
+    let mut number = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        secs = secs.checked_add(number.parse::<u64>().ok()?.checked_mul(unit)?)?;
+        number.clear();
+    }
+    if !number.is_empty() || secs == 0 {
+        return None;
+    }
+    Some(Duration::from_secs(secs))
+}
+
+/// Reads a 24 hour time like "17:00" or "17:00:30".
+pub fn parse_time(text: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(text, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(text, "%H:%M"))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_duration, Mode, Timer};
+    use chrono::{Local, NaiveTime};
+    use std::time::Duration;
+
+    #[test]
+    fn until_rings_once() {
+        let mut timer = Timer::from(Mode::Until(NaiveTime::from_hms_opt(12, 0, 0).unwrap()));
+        timer.deadline = Some(Local::now() - chrono::Duration::seconds(1));
+        assert!(timer.tick());
+        assert!(!timer.tick());
+        // it can't be paused or reset, so it can't ring again
+        timer.reset();
+        timer.toggle();
+        assert!(!timer.tick());
+        assert_eq!(timer.display(), "00:00");
+    }
+
+    #[test]
+    fn reads_units() {
+        assert_eq!(parse_duration("25"), Some(Duration::from_secs(25 * 60)));
+        assert_eq!(parse_duration("90s"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("1h30m"), Some(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("1m0s"), Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn refuses_zero() {
+        assert_eq!(parse_duration("0"), None);
+        assert_eq!(parse_duration("0s"), None);
+        assert_eq!(parse_duration("0h0m"), None);
+    }
+
+    #[test]
+    fn refuses_garbage() {
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("m"), None);
+        assert_eq!(parse_duration("5x"), None);
+        assert_eq!(parse_duration("10m5"), None);
+    }
+
+    #[test]
+    fn refuses_overflow() {
+        assert_eq!(parse_duration(&u64::MAX.to_string()), None);
+        assert_eq!(parse_duration(&format!("{}h", u64::MAX / 60)), None);
+        assert_eq!(parse_duration(&format!("{}s{}s", u64::MAX, 1)), None);
+    }
+}